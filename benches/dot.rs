@@ -0,0 +1,52 @@
+//! Matrix products of `DataMatrix`, against the generic path of `Mat::dot_unchecked`, which is
+//! taken here through a `SliceMatrix` covering the whole matrix
+//!
+//! Run with `cargo +nightly bench --bench dot`
+#![feature(test)]
+extern crate test;
+
+use mat::{Complex, DataMatrix, Mat, SliceMatrix};
+use test::{black_box, Bencher};
+
+const N: usize = 500;
+
+fn sample_f64() -> DataMatrix<f64> {
+    DataMatrix::new((0..N * N).map(|x| (x % 17) as f64 - 8.0).collect(), N, N).unwrap()
+}
+
+fn sample_complex() -> DataMatrix<Complex> {
+    DataMatrix::new((0..N * N).map(|x| Complex((x % 17) as f64 - 8.0, (x % 5) as f64)).collect(), N, N).unwrap()
+}
+
+#[bench]
+fn dot_f64_tiled(b: &mut Bencher) {
+    let a = sample_f64();
+    b.iter(|| black_box(a.dot(&a).unwrap()));
+}
+
+#[bench]
+fn dot_f64_tiled_transposed(b: &mut Bencher) {
+    let a = sample_f64();
+    let t = sample_f64().transposed();
+    b.iter(|| black_box(t.dot(&a).unwrap()));
+}
+
+#[bench]
+fn dot_f64_generic(b: &mut Bencher) {
+    let a = sample_f64();
+    let s = SliceMatrix::new(&a, 0, N, 0, N).unwrap();
+    b.iter(|| black_box(s.dot(&a).unwrap()));
+}
+
+#[bench]
+fn dot_complex_tiled(b: &mut Bencher) {
+    let a = sample_complex();
+    b.iter(|| black_box(a.dot(&a).unwrap()));
+}
+
+#[bench]
+fn dot_complex_generic(b: &mut Bencher) {
+    let a = sample_complex();
+    let s = SliceMatrix::new(&a, 0, N, 0, N).unwrap();
+    b.iter(|| black_box(s.dot(&a).unwrap()));
+}
@@ -39,13 +39,13 @@ fn example_concat() {
         2;
     ].convert();
 
-    let augmented = concated_mat![(&mut a) (&mut b);].unwrap();
+    let augmented = concated_mat![(&mut a as &mut dyn Mat<Item = _>) (&mut b as &mut dyn Mat<Item = _>);].unwrap();
     println!("Augmented Mat:\n{}", augmented);
 
-    let eliminated = augmented.eliminated();
+    let eliminated = augmented.eliminated().unwrap();
     println!("Eliminated:\n{}", eliminated);
 
-    let reduced = eliminated.reduced();
+    let reduced = eliminated.reduced().unwrap();
     println!("Reduced:\n{}", reduced);
 }
 
@@ -108,7 +108,7 @@ impl TryInto<MatrixOrTable> for &mut dyn Iterator<Item = &str> {
                                 match parsing_mode {
                                     ParsingMode::Complex => complexes.push(cpl),
                                     ParsingMode::Rational => {
-                                        complexes = rats.iter().map(|&x| f64::from(x).into()).collect();
+                                        complexes = rats.iter().map(|x| f64::from(x.clone()).into()).collect();
                                         complexes.push(cpl);
                                         parsing_mode = ParsingMode::Complex;
                                     }
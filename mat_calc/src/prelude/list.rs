@@ -14,7 +14,7 @@ use crate::eval::ObjectPairItem::*;
 pub fn car(args: ObjectPairItem, _: &mut Environment) -> Output {
     match args {
         List(pair) => return Ok(pair.first.clone()),
-        Lit(Rat(r)) => return Ok(Lit(Rat(Rational(r.0, 1)))),
+        Lit(Rat(r)) => return Ok(Lit(Rat(Rational::from(r.0)))),
         Lit(Cplx(c)) => return Ok(Lit(Cplx(c.re().into()))),
         _ => {
             return Err(EvalError::syntax(
@@ -28,7 +28,7 @@ pub fn car(args: ObjectPairItem, _: &mut Environment) -> Output {
 pub fn cdr(args: ObjectPairItem, _: &mut Environment) -> Output {
     match args {
         List(pair) => return Ok(pair.second.clone()),
-        Lit(Rat(r)) => return Ok(Lit(Rat(Rational(r.1, 1)))),
+        Lit(Rat(r)) => return Ok(Lit(Rat(Rational::from(r.1)))),
         Lit(Cplx(c)) => return Ok(Lit(Cplx(c.im().into()))),
         _ => {
             return Err(EvalError::syntax(
@@ -6,6 +6,7 @@ use mat::alg::SolveResult;
 use mat::element::LinearElem;
 use mat::element::Inv;
 use mat::error::MatError;
+use mat::Complex;
 use mat::ConcatedMatrix;
 use mat::DataMatrix;
 use mat::Mat;
@@ -37,11 +38,11 @@ pub fn inv(args: ObjectPairItem, _: &mut Environment) -> Output {
 pub fn eliminate(args: ObjectPairItem, _: &mut Environment) -> Output {
     match args {
         Lit(Matrix(MatrixWrap::Cpl(m))) => {
-            let r = m.clone_data().eliminated();
+            let r = m.clone_data().eliminated()?;
             return Ok(Lit(Matrix(MatrixWrap::Cpl(Rc::new(r)))));
         }
         Lit(Matrix(MatrixWrap::Rat(m))) => {
-            let r = m.clone_data().eliminated();
+            let r = m.clone_data().eliminated()?;
             return Ok(Lit(Matrix(MatrixWrap::Rat(Rc::new(r)))));
         }
         _ => return Err(EvalError::typ(format!("Can only eliminate a matrix"))),
@@ -56,11 +57,11 @@ pub fn reduce(args: ObjectPairItem, _: &mut Environment) -> Output {
             ))
         }
         Lit(Matrix(MatrixWrap::Cpl(m))) => {
-            let r = m.clone_data().eliminated().reduced();
+            let r = m.clone_data().eliminated()?.reduced()?;
             return Ok(Lit(Matrix(MatrixWrap::Cpl(Rc::new(r)))));
         }
         Lit(Matrix(MatrixWrap::Rat(m))) => {
-            let r = m.clone_data().eliminated().reduced();
+            let r = m.clone_data().eliminated()?.reduced()?;
             return Ok(Lit(Matrix(MatrixWrap::Rat(Rc::new(r)))));
         }
         _ => return Err(EvalError::typ(format!("Can only eliminate a matrix"))),
@@ -70,11 +71,11 @@ pub fn reduce(args: ObjectPairItem, _: &mut Environment) -> Output {
 pub fn rank(args: ObjectPairItem, _: &mut Environment) -> Output {
     match args {
         Lit(Matrix(MatrixWrap::Cpl(m))) => {
-            let r = m.clone_data().eliminated().rank() as i64;
+            let r = m.clone_data().eliminated()?.rank() as i64;
             return Ok(Lit(Rat(r.into())));
         }
         Lit(Matrix(MatrixWrap::Rat(m))) => {
-            let r = m.clone_data().eliminated().rank() as i64;
+            let r = m.clone_data().eliminated()?.rank() as i64;
             return Ok(Lit(Rat(r.into())));
         }
         _ => {
@@ -193,14 +194,14 @@ pub fn trace(args: ObjectPairItem, _: &mut Environment) -> Output {
 pub fn null_space(args: ObjectPairItem, _: &mut Environment) -> Output {
     match args {
         Lit(Matrix(MatrixWrap::Cpl(m))) => {
-            if let Some(ns) = m.clone_data().eliminated().null_space() {
+            if let Some(ns) = m.clone_data().eliminated()?.null_space()? {
                 return Ok(Lit(Matrix(MatrixWrap::Cpl(Rc::new(ns)))));
             } else {
                 return Ok(Lit(Nil));
             }
         }
         Lit(Matrix(MatrixWrap::Rat(m))) => {
-            if let Some(ns) = m.clone_data().eliminated().null_space() {
+            if let Some(ns) = m.clone_data().eliminated()?.null_space()? {
                 return Ok(Lit(Matrix(MatrixWrap::Rat(Rc::new(ns)))));
             } else {
                 return Ok(Lit(Nil));
@@ -217,7 +218,7 @@ pub fn null_space(args: ObjectPairItem, _: &mut Environment) -> Output {
 pub fn ridentity(args: ObjectPairItem, _: &mut Environment) -> Output {
     match args {
         Lit(Rat(r)) => {
-            if r.1 != 1 || r.0 < 0 {
+            if !r.is_integer() || r.0 < 0.into() {
                 return Err(EvalError::value(
                     "Need an positive integer, not a fraction".to_string(),
                 ));
@@ -233,7 +234,7 @@ pub fn ridentity(args: ObjectPairItem, _: &mut Environment) -> Output {
 pub fn cidentity(args: ObjectPairItem, _: &mut Environment) -> Output {
     match args {
         Lit(Rat(r)) => {
-            if r.1 != 1 || r.0 < 0 {
+            if !r.is_integer() || r.0 < 0.into() {
                 return Err(EvalError::value(
                     "Need an positive integer, not a fraction".to_string(),
                 ));
@@ -320,7 +321,7 @@ pub fn get(args: ObjectPairItem, _: &mut Environment) -> Output {
                     second: Lit(Rat(j)),
                 }),
         }) => {
-            if i.1 != 1 || j.1 != 1 {
+            if !i.is_integer() || !j.is_integer() {
                 return Err(EvalError::value(format!(
                     "Can only index into matrix by integers"
                 )));
@@ -341,14 +342,14 @@ pub fn get(args: ObjectPairItem, _: &mut Environment) -> Output {
                     second: Lit(Rat(j)),
                 }),
         }) => {
-            if i.1 != 1 || j.1 != 1 {
+            if !i.is_integer() || !j.is_integer() {
                 return Err(EvalError::value(format!(
                     "Can only index into matrix by integers"
                 )));
             }
             if let (Ok(ui), Ok(uj)) = (i.0.try_into(), j.0.try_into()) {
-                return Ok(Lit(Rat(*m
-                    .get(ui, uj) ?)));
+                return Ok(Lit(Rat(m
+                    .get(ui, uj) ?.clone())));
             } else {
                 return Err(EvalError::value(format!("Bad index")));
             }
@@ -364,43 +365,158 @@ pub fn get(args: ObjectPairItem, _: &mut Environment) -> Output {
 pub fn qr(args: ObjectPairItem, _: &mut Environment) -> Output {
     match args {
         Lit(Matrix(MatrixWrap::Cpl(m))) => {
-            let mut r: DataMatrix<f64> = m.clone_data().convert();
+            let mut r = m.clone_data();
             let q = alg::qr(&mut r)?;
             return Ok(List(Box::new(ObjectPair {
-                first: Lit(Matrix(MatrixWrap::Cpl(Rc::new(q.convert())))),
-                second: Lit(Matrix(MatrixWrap::Cpl(Rc::new(r.convert()))))
+                first: Lit(Matrix(MatrixWrap::Cpl(Rc::new(q)))),
+                second: Lit(Matrix(MatrixWrap::Cpl(Rc::new(r))))
             })));
         },
-        _ => return Err(EvalError::typ(format!("Can only QR decomposite a complex matrix with real values")))
+        _ => return Err(EvalError::typ(format!("Can only QR decomposite a complex matrix")))
     }
 }
 
+/// If all elements of `m` have zero imaginary part
+fn is_real(m: &dyn Mat<Item = Complex>) -> bool {
+    (0..m.rows()).all(|i| (0..m.cols()).all(|j| m.get(i, j).unwrap().im() == 0.0))
+}
+
 pub fn eigenmat(args: ObjectPairItem, _: &mut Environment) -> Output {
     match args {
         Lit(Matrix(MatrixWrap::Cpl(m))) => {
-            let m: DataMatrix<f64> = m.clone_data().convert();
-            let solver = alg::EigenValueSolver::new(m)?;
-            let m = solver.eigen_mat(1e-3, 999);
-            return Ok(Lit(Matrix(MatrixWrap::Cpl(Rc::new(m.convert())))));
+            let (m, report) = if is_real(m.as_ref()) {
+                let m: DataMatrix<f64> = m.clone_data().convert();
+                let (m, report) = alg::EigenValueSolver::new(m)?.eigen_mat_with_report(f64::EPSILON, 999);
+                (m.convert(), report)
+            } else {
+                alg::EigenValueSolver::new(m.clone_data())?.eigen_mat_with_report(f64::EPSILON, 999)
+            };
+            if !report.converged {
+                return Err(EvalError::value(format!("QR iteration didn't converge after {} steps", report.iterations)));
+            }
+            return Ok(Lit(Matrix(MatrixWrap::Cpl(Rc::new(m)))));
         },
-        _ => return Err(EvalError::typ(format!("Can only QR decomposite a complex matrix with real values")))
+        _ => return Err(EvalError::typ(format!("Can only QR decomposite a complex matrix")))
     }
 }
 
 pub fn eigenvalues(args: ObjectPairItem, _: &mut Environment) -> Output {
     match args{ 
         Lit(Matrix(MatrixWrap::Cpl(m))) => {
-            let m: DataMatrix<f64> = m.clone_data().convert();
-            let solver = alg::EigenValueSolver::new(m)?;
-            let eigen_vals = solver.eigen_values(1e-3, 999);
+            let (eigen_vals, report) = if is_real(m.as_ref()) {
+                let m: DataMatrix<f64> = m.clone_data().convert();
+                alg::EigenValueSolver::new(m)?.eigen_values_with_report(f64::EPSILON, 999)
+            } else {
+                alg::EigenValueSolver::new(m.clone_data())?.eigen_values_with_report(f64::EPSILON, 999)
+            };
+            if !report.converged {
+                return Err(EvalError::value(format!("QR iteration didn't converge after {} steps", report.iterations)));
+            }
             let len = eigen_vals.len();
             let eigen_vals = DataMatrix::new(eigen_vals, len, 1).unwrap();
             return Ok(Lit(Matrix(MatrixWrap::Cpl(Rc::new(eigen_vals)))));
         },
-        _ => return Err(EvalError::typ(format!("Can only calculate eigenvalues of a complex matrix with real values")))
+        _ => return Err(EvalError::typ(format!("Can only calculate eigenvalues of a complex matrix")))
     }
 }
 
+pub fn eigen(args: ObjectPairItem, _: &mut Environment) -> Output {
+    match args {
+        Lit(Matrix(MatrixWrap::Cpl(m))) => {
+            let (eigen_vals, eigen_vecs) = if is_real(m.as_ref()) {
+                let m: DataMatrix<f64> = m.clone_data().convert();
+                alg::eigenvectors(&m, f64::EPSILON, 999)?
+            } else {
+                alg::eigenvectors(m.as_ref(), f64::EPSILON, 999)?
+            };
+            let len = eigen_vals.len();
+            let eigen_vals = DataMatrix::new(eigen_vals, len, 1).unwrap();
+            return Ok(List(Box::new(ObjectPair {
+                first: Lit(Matrix(MatrixWrap::Cpl(Rc::new(eigen_vals)))),
+                second: Lit(Matrix(MatrixWrap::Cpl(Rc::new(eigen_vecs))))
+            })));
+        },
+        _ => return Err(EvalError::typ(format!("Can only calculate eigenvectors of a complex matrix")))
+    }
+}
+
+pub fn charpoly(args: ObjectPairItem, _: &mut Environment) -> Output {
+    match args {
+        Lit(Matrix(MatrixWrap::Cpl(m))) => {
+            let p = alg::charpoly(m.as_ref())?;
+            return Ok(Lit(Matrix(MatrixWrap::Cpl(Rc::new(DataMatrix::one_col(p.coef))))));
+        }
+        Lit(Matrix(MatrixWrap::Rat(m))) => {
+            let p = alg::charpoly(m.as_ref())?;
+            return Ok(Lit(Matrix(MatrixWrap::Rat(Rc::new(DataMatrix::one_col(p.coef))))));
+        }
+        _ => return Err(EvalError::typ(format!("Can only calculate characteristic polynomial of a matrix")))
+    }
+}
+
+pub fn minpoly(args: ObjectPairItem, _: &mut Environment) -> Output {
+    match args {
+        Lit(Matrix(MatrixWrap::Rat(m))) => {
+            let p = alg::minimal_polynomial(m.as_ref())?;
+            return Ok(Lit(Matrix(MatrixWrap::Rat(Rc::new(DataMatrix::one_col(p.coef))))));
+        }
+        _ => return Err(EvalError::typ(format!("Can only calculate minimal polynomial of a rational matrix")))
+    }
+}
+
+pub fn jordan(args: ObjectPairItem, _: &mut Environment) -> Output {
+    match args {
+        Lit(Matrix(MatrixWrap::Rat(m))) => {
+            let (p, j) = alg::jordan_form(m.as_ref())?;
+            return Ok(List(Box::new(ObjectPair {
+                first: Lit(Matrix(MatrixWrap::Rat(Rc::new(p)))),
+                second: Lit(Matrix(MatrixWrap::Rat(Rc::new(j))))
+            })));
+        }
+        _ => return Err(EvalError::typ(format!("Can only calculate Jordan form of a rational matrix")))
+    }
+}
+
+/// Apply a matrix function on a complex matrix, done in reals if the matrix is real
+fn matrix_function(
+    args: ObjectPairItem,
+    name: &str,
+    real: fn(&dyn Mat<Item = f64>) -> Result<DataMatrix<f64>, MatError>,
+    complex: fn(&dyn Mat<Item = Complex>) -> Result<DataMatrix<Complex>, MatError>,
+) -> Output {
+    match args {
+        Lit(Matrix(MatrixWrap::Cpl(m))) => {
+            let real_result = if is_real(m.as_ref()) {
+                let r: DataMatrix<f64> = m.clone_data().convert();
+                match real(&r) {
+                    Err(MatError::ComplexResult) => None,
+                    r => Some(r?.convert()),
+                }
+            } else {
+                None
+            };
+            let r = match real_result {
+                Some(r) => r,
+                None => complex(m.as_ref())?,
+            };
+            return Ok(Lit(Matrix(MatrixWrap::Cpl(Rc::new(r)))));
+        }
+        _ => return Err(EvalError::typ(format!("Can only calculate {} of a complex matrix", name)))
+    }
+}
+
+pub fn expm(args: ObjectPairItem, _: &mut Environment) -> Output {
+    matrix_function(args, "expm", alg::expm, alg::expm)
+}
+
+pub fn sqrtm(args: ObjectPairItem, _: &mut Environment) -> Output {
+    matrix_function(args, "sqrtm", alg::sqrtm, alg::sqrtm)
+}
+
+pub fn logm(args: ObjectPairItem, _: &mut Environment) -> Output {
+    matrix_function(args, "logm", alg::logm, alg::logm)
+}
+
 pub fn diag(args: ObjectPairItem, _: &mut Environment) -> Output {
     match args {
         Lit(Matrix(MatrixWrap::Cpl(m))) => {
@@ -448,7 +564,7 @@ pub fn slice(args: ObjectPairItem, _: &mut Environment) -> Output {
                 Lit(Rat(rb)),
                 Lit(Matrix(MatrixWrap::Cpl(m))),
             ] => {
-                if rb.1 != 1 || rows.1 != 1 || cb.1 != 1 || cols.1 != 1 {
+                if !rb.is_integer() || !rows.is_integer() || !cb.is_integer() || !cols.is_integer() {
                     return Err(EvalError::typ(format!("Rows and cols need to be integers")));
                 }
                 let [rb, rows, cb, cols] = [rb, rows, cb, cols].map(|x| x.0.try_into().unwrap());
@@ -464,7 +580,7 @@ pub fn slice(args: ObjectPairItem, _: &mut Environment) -> Output {
                 Lit(Rat(rb)),
                 Lit(Matrix(MatrixWrap::Rat(m))),
             ] => {
-                if rb.1 != 1 || rows.1 != 1 || cb.1 != 1 || cols.1 != 1 {
+                if !rb.is_integer() || !rows.is_integer() || !cb.is_integer() || !cols.is_integer() {
                     return Err(EvalError::typ(format!("Rows and cols need to be integers")));
                 }
                 let [rb, rows, cb, cols] = [rb, rows, cb, cols].map(|x| x.0.try_into().unwrap());
@@ -499,17 +615,16 @@ pub fn orthogonalize(args: ObjectPairItem, _: &mut Environment) -> Output {
 pub fn normalize_cols(args: ObjectPairItem, _: &mut Environment) -> Output {
     match args {
         Lit(Matrix(MatrixWrap::Cpl(m))) => {
-            let ret = m.clone_data();
-            let mut ret: DataMatrix<f64> = ret.convert();
+            let mut ret = m.clone_data();
             alg::normalize_cols(&mut ret);
-            return Ok(Lit(Matrix(MatrixWrap::Cpl(Rc::new(ret.convert())))));
+            return Ok(Lit(Matrix(MatrixWrap::Cpl(Rc::new(ret)))));
         },
         _ => return Err(EvalError::typ(format!("Need a complex matrix as argument")))
     }
  
 }
 
-pub const EXPORTS: [BuiltinFunction; 21] = [
+pub const EXPORTS: [BuiltinFunction; 28] = [
     BuiltinFunction {
         f: &inv,
         argn: 1,
@@ -610,9 +725,8 @@ pub const EXPORTS: [BuiltinFunction; 21] = [
         argn: 1,
         help: indoc! {"
             Calculate the QR decomposition of a matrix.
-            The matrix must have data type complex, but this algorithim can't handle
-            complex matrixes, so the input matrix is cast into real matrix by taking
-            the real part of each element.
+            The matrix must have data type complex, `Q` is unitary and `R` is
+            upper-triangle.
             Returns `(Q R)`."}
     },
     BuiltinFunction {
@@ -620,11 +734,10 @@ pub const EXPORTS: [BuiltinFunction; 21] = [
         name: "eigmat",
         argn: 1,
         help: indoc! {"
-            Calculate the eigenvalues of a matrix. The matrix must be complex, 
-            but only the real part of each element is taken due to limitation of
-            the algorithim.
+            Calculate the eigenvalues of a matrix. The matrix must be complex.
             This function returns a matrix, where elements below the diagnol are
-            zeros, and any 1x1 block on the diagnol is a real eigenvalue, and
+            zeros, and any 1x1 block on the diagnol is an eigenvalue.
+            If all elements are real, the calculation is done in real numbers, and
             any 2x2 block on the diagnol represents two adjoint complex eigenvalues"}
     },
     BuiltinFunction {
@@ -634,8 +747,73 @@ pub const EXPORTS: [BuiltinFunction; 21] = [
         help: indoc! {"
             Calculate and return the eigenvalues of a matrix in the form of a column
             vector.
-            The matrix must be complex, but only the real part of each element is
-            taken."}
+            The matrix must be complex."}
+    },
+    BuiltinFunction {
+        f: &eigen,
+        name: "eigen",
+        argn: 1,
+        help: indoc! {"
+            Usage: (eigen m: matrix) -> (values vectors)
+            Calculate the eigenvalues of a complex matrix as a column vector, and the
+            eigenvectors as columns of a matrix, each normalized.
+            Unlike `eigvecof`, this works with inexact float eigenvalues. The vectors
+            of a repeated eigenvalue are kept orthogonal."}
+    },
+    BuiltinFunction {
+        f: &charpoly,
+        name: "charpoly",
+        argn: 1,
+        help: indoc! {"
+            Usage: (charpoly m: matrix) -> matrix
+            Calculate the characteristic polynomial `det(xI - m)`, returning its
+            coefficients from low to high as a column vector.
+            For a rational matrix the result is exact."}
+    },
+    BuiltinFunction {
+        f: &minpoly,
+        name: "minpoly",
+        argn: 1,
+        help: indoc! {"
+            Usage: (minpoly m: matrix) -> matrix
+            Calculate the minimal polynomial of a rational matrix, returning its
+            coefficients from low to high as a column vector."}
+    },
+    BuiltinFunction {
+        f: &jordan,
+        name: "jordan",
+        argn: 1,
+        help: indoc! {"
+            Usage: (jordan m: matrix) -> (P J)
+            Calculate the Jordan normal form of a rational matrix, whose eigenvalues
+            must be all rational. Returns `(P J)` where `m = P J P^-1`."}
+    },
+    BuiltinFunction {
+        f: &expm,
+        name: "expm",
+        argn: 1,
+        help: indoc! {"
+            Usage: (expm m: matrix) -> matrix
+            Calculate the matrix exponential `e^m` of a complex matrix.
+            The solution of `x' = Ax` is `(* (expm (* A t)) x0)`."}
+    },
+    BuiltinFunction {
+        f: &sqrtm,
+        name: "sqrtm",
+        argn: 1,
+        help: indoc! {"
+            Usage: (sqrtm m: matrix) -> matrix
+            Calculate the principal square root of a complex matrix, whose
+            eigenvalues have non-negative real parts."}
+    },
+    BuiltinFunction {
+        f: &logm,
+        name: "logm",
+        argn: 1,
+        help: indoc! {"
+            Usage: (logm m: matrix) -> matrix
+            Calculate the principal logarithm of a complex matrix, so that
+            `(expm (logm m))` is `m`. Singular matrices have no logarithm."}
     },
     BuiltinFunction {
         f: &diag,
@@ -674,7 +852,6 @@ pub const EXPORTS: [BuiltinFunction; 21] = [
         name: "normalize",
         argn: 1,
         help: indoc! {"
-            Normalize the columns of a float matrix, which is represented using the
-            real part of a complex matrix"}
+            Normalize the columns of a complex matrix"}
     }
 ];
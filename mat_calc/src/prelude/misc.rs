@@ -1,6 +1,6 @@
 use super::{all_builtins, Output};
 use indoc::indoc;
-use mat::Rational;
+use mat::element::{AddZero, MulZero};
 
 use crate::eval::{BuiltinFunction, Environment, ObjectPairItem};
 
@@ -83,10 +83,10 @@ pub fn help(args: ObjectPairItem, env: &mut Environment) -> Output {
             let help_name = format!("_help_{}", f.name);
             return env.find_object(&help_name).map_or(Ok(Lit(Str(format!("No help info for {}", f.name)))), |o| Ok(o));
         }
-        Lit(Rat(Rational(0, 1))) => {
+        Lit(Rat(r)) if r.is_add_zero() => {
             return Ok(Lit(Str(GENERAL_HELP.to_string())));
         },
-        Lit(Rat(Rational(1, 1))) => {
+        Lit(Rat(r)) if r.is_mul_zero() => {
             let names: Vec<&str> = all_builtins().map(|b| b.name).collect();
             return Ok(Lit(Str(format!(
                 "LIST OF AVAILABLE BUILTINS\n{}\n{}",
@@ -105,12 +105,12 @@ pub fn help(args: ObjectPairItem, env: &mut Environment) -> Output {
 pub fn maxrecur(args: ObjectPairItem, env: &mut Environment) -> Output {
     match args {
         Lit(Rat(r)) => {
-            if r.1 != 1 {
+            if !r.is_integer() {
                 return Err(EvalError::value(format!(
                     "Can only set max recursion to integer"
                 )));
             }
-            if r.0 == 0 {
+            if r.is_add_zero() {
                 let x: i64 = env.config.max_recursion.try_into().map_err(|_| {
                     EvalError::value(format!(
                         "Current recursion limit is too large to represent in i64"
@@ -1,5 +1,6 @@
 use indoc::indoc;
 use mat::Complex;
+use mat::element::AddZero;
 use mat::DataMatrix;
 use mat::Mat;
 use std::rc::Rc;
@@ -20,12 +21,12 @@ pub fn add(args: ObjectPairItem, _: &mut Environment) -> Output {
         List(pair) => match (&pair.first, &pair.second) {
             (Lit(a), Lit(b)) => match (a, b) {
                 (Cplx(a), Cplx(b)) => return Ok(Lit(Cplx(*a + b))),
-                (Rat(a), Rat(b)) => return Ok(Lit(Rat(*a + b))),
+                (Rat(a), Rat(b)) => return Ok(Lit(Rat(a.clone() + b))),
                 (Matrix(MatrixWrap::Cpl(a)), Matrix(MatrixWrap::Cpl(b))) => {
-                    return Ok(Lit(Matrix(MatrixWrap::Cpl(Rc::new(a.add(b.as_ref()))))));
+                    return Ok(Lit(Matrix(MatrixWrap::Cpl(Rc::new(a.try_add(b.as_ref())?)))));
                 }
                 (Matrix(MatrixWrap::Rat(a)), Matrix(MatrixWrap::Rat(b))) => {
-                    return Ok(Lit(Matrix(MatrixWrap::Rat(Rc::new(a.add(b.as_ref()))))));
+                    return Ok(Lit(Matrix(MatrixWrap::Rat(Rc::new(a.try_add(b.as_ref())?)))));
                 }
                 (Cplx(a), Matrix(MatrixWrap::Cpl(b))) => {
                     let mut b = b.clone_data();
@@ -50,12 +51,12 @@ pub fn sub(args: ObjectPairItem, _: &mut Environment) -> Output {
         List(pair) => match (&pair.first, &pair.second) {
             (Lit(a), Lit(b)) => match (a, b) {
                 (Cplx(a), Cplx(b)) => return Ok(Lit(Cplx(*a - b))),
-                (Rat(a), Rat(b)) => return Ok(Lit(Rat(*a - b))),
+                (Rat(a), Rat(b)) => return Ok(Lit(Rat(a.clone() - b))),
                 (Matrix(MatrixWrap::Cpl(a)), Matrix(MatrixWrap::Cpl(b))) => {
-                    return Ok(Lit(Matrix(MatrixWrap::Cpl(Rc::new(a.sub(b.as_ref()))))));
+                    return Ok(Lit(Matrix(MatrixWrap::Cpl(Rc::new(a.try_sub(b.as_ref())?)))));
                 }
                 (Matrix(MatrixWrap::Rat(a)), Matrix(MatrixWrap::Rat(b))) => {
-                    return Ok(Lit(Matrix(MatrixWrap::Rat(Rc::new(a.sub(b.as_ref()))))));
+                    return Ok(Lit(Matrix(MatrixWrap::Rat(Rc::new(a.try_sub(b.as_ref())?)))));
                 }
                 (a, b) => {
                     return Err(EvalError::typ(format!(
@@ -84,7 +85,7 @@ pub fn times(args: ObjectPairItem, _: &mut Environment) -> Output {
         List(pair) => match (&pair.first, &pair.second) {
             (Lit(a), Lit(b)) => match (a, b) {
                 (Cplx(a), Cplx(b)) => return Ok(Lit(Cplx(*a * b))),
-                (Rat(a), Rat(b)) => return Ok(Lit(Rat(*a * b))),
+                (Rat(a), Rat(b)) => return Ok(Lit(Rat(a.clone() * b))),
                 (Matrix(MatrixWrap::Cpl(a)), Matrix(MatrixWrap::Cpl(b))) => {
                     match a.dot(b.as_ref()) {
                         Ok(r) => return Ok(Lit(Matrix(MatrixWrap::Cpl(Rc::new(r))))),
@@ -130,10 +131,10 @@ pub fn devide(args: ObjectPairItem, _: &mut Environment) -> Output {
                     return Ok(Lit(Cplx(*a / b)));
                 },
                 (Rat(a), Rat(b)) => {
-                    if b.0 == 0 {
+                    if b.is_add_zero() {
                         return Err(EvalError::zero_division(format!("{}/0", a)));
                     }
-                    return Ok(Lit(Rat(*a / b)));
+                    return Ok(Lit(Rat(a.clone() / b)));
                 }
                 (a, b) => return Err(EvalError::typ(format!("Can't devide `{}` and `{}`", a, b))),
             },
@@ -395,7 +395,7 @@ mod parsing {
                 }) => {
                     assert_eq!(word, "x");
                     assert_eq!(m.dimensions(), (1, 1));
-                    assert_eq!(m.get(0, 0).unwrap(), &Rational(1, 1));
+                    assert_eq!(m.get(0, 0).unwrap(), &Rational::from(1));
                 }
                 _ => panic!("Unexpected pair: {pair_item}"),
             }
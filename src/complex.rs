@@ -25,6 +25,13 @@ impl Complex {
     }
     pub fn re(&self) -> f64 { self.0 }
     pub fn im(&self) -> f64 { self.1 }
+    /// The principal square root, with non-negative real part
+    pub fn sqrt(self) -> Self {
+        let r = self.normal();
+        let re = ((r + self.0) / 2.0).sqrt();
+        let im = ((r - self.0) / 2.0).sqrt();
+        Self(re, if self.1 < 0.0 { -im } else { im })
+    }
 }
 
 impl Add<&Self> for Complex {
@@ -60,8 +67,7 @@ impl Mul<&Self> for Complex {
 }
 impl MulAssign<&Self> for Complex {
     fn mul_assign(&mut self, rhs: &Self) {
-        self.0 = self.0 * rhs.0 - self.1 * rhs.1;
-        self.1 = self.0 * rhs.1 + self.1 * rhs.0;
+        *self = *self * rhs;
     }
 }
 impl Div<&Self> for Complex {
@@ -161,5 +167,23 @@ mod impl_linear {
     }
 
 
-    impl LinearElem for Complex {}
+    impl LinearElem for Complex {
+        par_row_blocks!();
+    }
+    impl Normal for Complex {
+        fn normal(&self) -> f64 {
+            Complex::normal(self)
+        }
+    }
+    impl FloatElem for Complex {
+        fn from_f64(x: f64) -> Self {
+            Self(x, 0.0)
+        }
+        fn re(&self) -> f64 {
+            self.0
+        }
+        fn conj(&self) -> Self {
+            self.adjoint()
+        }
+    }
 }
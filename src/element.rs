@@ -1,3 +1,7 @@
+#[cfg(feature = "parallel")]
+use crate::error::MatError;
+#[cfg(feature = "parallel")]
+use crate::matrix::RowBlockMut;
 use std::cmp::PartialEq;
 use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Sub, SubAssign};
 
@@ -19,13 +23,18 @@ pub trait Inv {
 }
 impl<T> Inv for T
 where
-    T: Copy + for<'a> Div<&'a Self, Output = T> + MulZero,
+    T: Clone + for<'a> Div<&'a Self, Output = T> + MulZero,
 {
     fn inv(self) -> Self {
         Self::mul_zero() / &self
     }
 }
 
+/// Species the magnitude `|a|` of an element, used for example to choose pivots
+pub trait Normal {
+    fn normal(&self) -> f64;
+}
+
 pub trait LinearElem:
     Sized
     + Clone
@@ -41,6 +50,75 @@ pub trait LinearElem:
     + std::fmt::Display
     + std::fmt::Debug
 {
+    /// `self + rhs`, or [`None`] if the result can't be represented
+    ///
+    /// By default the operation is assumed never to overflow
+    fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        Some(self.clone() + rhs)
+    }
+    /// `self - rhs`, or [`None`] if the result can't be represented
+    fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        Some(self.clone() - rhs)
+    }
+    /// `self * rhs`, or [`None`] if the result can't be represented
+    fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        Some(self.clone() * rhs)
+    }
+
+    /// Run `f` on `blocks` of a large matrix, stopping at the first error
+    ///
+    /// Visits them in order by default. The elements of this crate that are [`Send`] and
+    /// [`Sync`] override it to visit them across threads
+    ///
+    /// # Safety
+    ///
+    /// `f` must only capture elements, containers of them and plain data, so that it can be
+    /// shared across threads whenever `Self` can
+    #[cfg(feature = "parallel")]
+    #[doc(hidden)]
+    unsafe fn par_try_for_each_row_block<F>(blocks: Vec<RowBlockMut<'_, Self>>, f: F) -> Result<(), MatError>
+    where
+        F: Fn(RowBlockMut<'_, Self>) -> Result<(), MatError>,
+    {
+        blocks.into_iter().try_for_each(f)
+    }
+}
+
+/// Overrides [`LinearElem::par_try_for_each_row_block`] in an impl for an element that is
+/// [`Send`] and [`Sync`], so that its matrices are updated across threads
+macro_rules! par_row_blocks {
+    () => {
+        #[cfg(feature = "parallel")]
+        unsafe fn par_try_for_each_row_block<F>(
+            blocks: Vec<$crate::matrix::RowBlockMut<'_, Self>>,
+            f: F,
+        ) -> Result<(), $crate::error::MatError>
+        where
+            F: Fn($crate::matrix::RowBlockMut<'_, Self>) -> Result<(), $crate::error::MatError>,
+        {
+            $crate::matrix::par_try_for_each(blocks, f)
+        }
+    };
+}
+pub(crate) use par_row_blocks;
+
+/// Elements approximating real or complex numbers, for algorithms that need square roots or
+/// conjugates
+pub trait FloatElem: LinearElem + Inv + Normal {
+    /// Embed a real number
+    fn from_f64(x: f64) -> Self;
+    /// The real part
+    fn re(&self) -> f64;
+    /// Complex conjugate, which is itself for reals
+    fn conj(&self) -> Self;
+}
+
+/// Integers, for algorithms that need division with remainder instead of inverses
+pub trait IntegerElem: LinearElem + Ord {
+    /// Quotient `q` of Euclidean division, so that `self - q * rhs` is in `0..|rhs|`
+    ///
+    /// Returns [`None`] if `rhs` is zero or the result overflows
+    fn checked_div_euclid(&self, rhs: &Self) -> Option<Self>;
 }
 
 /// Implements [`LinearElem`] for some primitives
@@ -65,7 +143,25 @@ mod implementations {
                 (self - 1.0).abs() <= 1e-6
             }
         }
-        impl LinearElem for f64 {}
+        impl LinearElem for f64 {
+            par_row_blocks!();
+        }
+        impl Normal for f64 {
+            fn normal(&self) -> f64 {
+                self.abs()
+            }
+        }
+        impl FloatElem for f64 {
+            fn from_f64(x: f64) -> Self {
+                x
+            }
+            fn re(&self) -> f64 {
+                *self
+            }
+            fn conj(&self) -> Self {
+                *self
+            }
+        }
     }
 
     mod impl_f32 {
@@ -87,7 +183,14 @@ mod implementations {
                 (self - 1.0).abs() <= 1e-6
             }
         }
-        impl LinearElem for f32 {}
+        impl LinearElem for f32 {
+            par_row_blocks!();
+        }
+        impl Normal for f32 {
+            fn normal(&self) -> f64 {
+                self.abs() as f64
+            }
+        }
     }
 
     mod impl_i64 {
@@ -109,7 +212,23 @@ mod implementations {
                 *self == 1
             }
         }
-        impl LinearElem for i64 {}
+        impl LinearElem for i64 {
+            par_row_blocks!();
+            fn checked_add(&self, rhs: &Self) -> Option<Self> {
+                i64::checked_add(*self, *rhs)
+            }
+            fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+                i64::checked_sub(*self, *rhs)
+            }
+            fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+                i64::checked_mul(*self, *rhs)
+            }
+        }
+        impl IntegerElem for i64 {
+            fn checked_div_euclid(&self, rhs: &Self) -> Option<Self> {
+                i64::checked_div_euclid(*self, *rhs)
+            }
+        }
     }
     mod impl_i32 {
         use super::*;
@@ -130,6 +249,57 @@ mod implementations {
                 *self == 1
             }
         }
-        impl LinearElem for i32 {}
+        impl LinearElem for i32 {
+            par_row_blocks!();
+            fn checked_add(&self, rhs: &Self) -> Option<Self> {
+                i32::checked_add(*self, *rhs)
+            }
+            fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+                i32::checked_sub(*self, *rhs)
+            }
+            fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+                i32::checked_mul(*self, *rhs)
+            }
+        }
+        impl IntegerElem for i32 {
+            fn checked_div_euclid(&self, rhs: &Self) -> Option<Self> {
+                i32::checked_div_euclid(*self, *rhs)
+            }
+        }
+    }
+    #[cfg(feature = "big_rational")]
+    mod impl_bigint {
+        use super::*;
+        use num_bigint::BigInt;
+        use num_traits::{Euclid, One, Zero};
+
+        impl AddZero for BigInt {
+            fn add_zero() -> Self {
+                BigInt::zero()
+            }
+            fn is_add_zero(&self) -> bool {
+                self.is_zero()
+            }
+        }
+        impl MulZero for BigInt {
+            fn mul_zero() -> Self {
+                BigInt::one()
+            }
+            fn is_mul_zero(&self) -> bool {
+                self.is_one()
+            }
+        }
+        impl LinearElem for BigInt {
+            par_row_blocks!();
+        }
+        impl IntegerElem for BigInt {
+            fn checked_div_euclid(&self, rhs: &Self) -> Option<Self> {
+                if rhs.is_zero() {
+                    None
+                } else {
+                    Some(Euclid::div_euclid(self, rhs))
+                }
+            }
+        }
     }
 }
@@ -30,7 +30,23 @@ pub enum MatError {
     /// Empty
     EmptyMatrix,
     /// Expected Vector
-    ExpectedVector
+    ExpectedVector,
+    /// Result of an arithmetic operation can't be represented by the element type
+    Overflow,
+    /// Expected a symmetric (or hermitian) positive definite matrix
+    NotPositiveDefinite,
+    /// Expected a symmetric matrix
+    NotSymmetric,
+    /// Some eigen values are not rational, so the result can't be represented exactly
+    IrrationalSpectrum,
+    /// An iterative algorithm didn't converge within the allowed steps
+    NotConverged { iterations: usize },
+    /// A matrix function isn't defined on the matrix, like the logarithm of a singular one
+    NotInDomain { function: &'static str },
+    /// The result is complex, so it can't be represented by real elements
+    ComplexResult,
+    /// Arrays given to build a sparse matrix don't fit together
+    BadSparseFormat(&'static str),
 }
 
 impl Display for MatError {
@@ -66,6 +82,14 @@ impl Display for MatError {
             ConcatFailure(s) => write!(f, "{s}"),
             EmptyMatrix => write!(f, "Empty matrix"),
             ExpectedVector => write!(f, "Expected vector"),
+            Overflow => write!(f, "Arithmetic overflow, retry with big rationals"),
+            NotPositiveDefinite => write!(f, "Matrix is not positive definite"),
+            NotSymmetric => write!(f, "Matrix is not symmetric"),
+            IrrationalSpectrum => write!(f, "Eigenvalues of the matrix are not all rational"),
+            NotConverged { iterations } => write!(f, "Didn't converge after {} steps", iterations),
+            NotInDomain { function } => write!(f, "Matrix is not in the domain of {}", function),
+            ComplexResult => write!(f, "Result is not real, retry with complex matrices"),
+            BadSparseFormat(s) => write!(f, "Bad sparse format: {}", s),
         }
     }
 }
@@ -27,7 +27,9 @@ pub use mat_macro::mat;
 // Exports
 pub use matrix::alg;
 pub use matrix::MatBlock;
-pub use matrix::{ConcatedMatrix, DataMatrix, EliminatedMatrix, Mat, SliceMatrix};
+pub use matrix::{ConcatedMatrix, DataMatrix, RowBlockMut, EliminatedMatrix, Mat, SliceMatrix, SparseMatrix};
+#[cfg(feature = "parallel")]
+pub use matrix::set_parallel_threshold;
 pub use polynomial::Polynomial;
 pub use rational::Rational;
 pub use complex::Complex;
@@ -8,20 +8,30 @@ mod concated_matrix;
 mod data_matrix;
 mod display;
 mod eliminated_matrix;
+mod iter;
 mod mat_block;
+mod ops;
 mod slice_matrix;
+mod sparse_matrix;
 
 #[cfg(test)]
 mod test;
 
 pub use concated_matrix::ConcatedMatrix;
-pub use data_matrix::DataMatrix;
+pub use data_matrix::{DataMatrix, RowBlockMut};
+pub(crate) use data_matrix::{for_each_row_block, try_for_each_row_block};
+#[cfg(feature = "parallel")]
+pub(crate) use data_matrix::par_try_for_each;
+#[cfg(feature = "parallel")]
+pub use data_matrix::set_parallel_threshold;
 pub use display::{mat_print_buf, mat_to_string};
 use element::*;
 pub use eliminated_matrix::EliminatedMatrix;
+pub use iter::{ColsIter, IndexedIter, Iter, IterMut, RowsIter};
 pub use mat_block::MatBlock;
 pub use slice_matrix::SliceMatrix;
 pub use slice_matrix::RcSliceMatrix;
+pub use sparse_matrix::SparseMatrix;
 
 /// The trait for a *matrix* that supports linear operations
 pub trait Mat {
@@ -196,6 +206,9 @@ pub trait Mat {
     ///
     /// If `rhs` is too large, it's truncated; If `rhs` is not large enough, rows and cols are repeated.
     /// More specificly, `(A + B)[ij] = A[ij] + B[i % b.rows(), j % b.cols()]`
+    ///
+    /// Use [`Mat::try_add`] to check the dimension, or [`Mat::broadcast_add`] to repeat a row or
+    /// a col on purpose
     unsafe fn add_assign_unchecked(&mut self, rhs: &dyn Mat<Item = Self::Item>) {
         for i in 0..self.rows() {
             for j in 0..self.cols() {
@@ -235,6 +248,35 @@ pub trait Mat {
         r
     }
 
+    /// `self + rhs`, or [`MatError::InconsistentDimension`] if their dimensions differ
+    fn try_add(&self, rhs: &dyn Mat<Item = Self::Item>) -> Result<DataMatrix<Self::Item>, MatError> {
+        if self.dimensions() != rhs.dimensions() {
+            return Err(InconsistentDimension { need: self.dimensions(), got: rhs.dimensions() });
+        }
+        Ok(self.add(rhs))
+    }
+    /// `self - rhs`, or [`MatError::InconsistentDimension`] if their dimensions differ
+    fn try_sub(&self, rhs: &dyn Mat<Item = Self::Item>) -> Result<DataMatrix<Self::Item>, MatError> {
+        if self.dimensions() != rhs.dimensions() {
+            return Err(InconsistentDimension { need: self.dimensions(), got: rhs.dimensions() });
+        }
+        Ok(self.sub(rhs))
+    }
+
+    /// Add `rhs` to every row of `self` if it's a row, to every col if it's a col, or to every
+    /// element if it's `1x1`. Same as [`Mat::try_add`] if the dimensions are equal
+    ///
+    /// Other dimensions give [`MatError::InconsistentDimension`]
+    fn broadcast_add(&self, rhs: &dyn Mat<Item = Self::Item>) -> Result<DataMatrix<Self::Item>, MatError> {
+        check_broadcast(self.dimensions(), rhs.dimensions())?;
+        Ok(self.add(rhs))
+    }
+    /// Same as `broadcast_add`, but subtracts
+    fn broadcast_sub(&self, rhs: &dyn Mat<Item = Self::Item>) -> Result<DataMatrix<Self::Item>, MatError> {
+        check_broadcast(self.dimensions(), rhs.dimensions())?;
+        Ok(self.sub(rhs))
+    }
+
     /// Returns a [`SliceMatrix`] refering to the `i`th row of `self`
     ///
     /// The [`SliceMatrix`] is not affected by `transpose()`
@@ -256,6 +298,108 @@ pub trait Mat {
         slice_matrix::SliceMatrix::new_unchecked(self, 0, self.rows(), j, 1)
     }
 
+    /// Iterate over the elements row by row, taken transpose into consideration
+    ///
+    /// Trait objects can't call this, use [`Iter::new`] for them
+    fn iter(&self) -> Iter<'_, Self::Item>
+    where
+        Self: Sized,
+    {
+        Iter::new(self)
+    }
+    /// Same as `iter`, but yields `((i, j), element)`
+    ///
+    /// Trait objects can't call this, use [`IndexedIter::new`] for them
+    fn indexed_iter(&self) -> IndexedIter<'_, Self::Item>
+    where
+        Self: Sized,
+    {
+        IndexedIter::new(self)
+    }
+    /// Iterate over the rows as [`SliceMatrix`]
+    ///
+    /// Trait objects can't call this, use [`RowsIter::new`] for them
+    fn rows_iter(&self) -> RowsIter<'_, Self::Item>
+    where
+        Self: Sized,
+    {
+        RowsIter::new(self)
+    }
+    /// Iterate over the cols as [`SliceMatrix`]
+    ///
+    /// Trait objects can't call this, use [`ColsIter::new`] for them
+    fn cols_iter(&self) -> ColsIter<'_, Self::Item>
+    where
+        Self: Sized,
+    {
+        ColsIter::new(self)
+    }
+
+    /// Apply `f` to every element, collecting the results into a new [`DataMatrix`]
+    ///
+    /// Trait objects can't call this, call it on their `view()` instead
+    fn map<U, F>(&self, f: F) -> DataMatrix<U>
+    where
+        Self: Sized,
+        U: LinearElem,
+        F: FnMut(&Self::Item) -> U,
+    {
+        unsafe { DataMatrix::new_unchecked(self.iter().map(f).collect(), self.rows(), self.cols()) }
+    }
+    /// Apply `f` to every element in place
+    ///
+    /// [`SparseMatrix`] only applies it to its stored elements
+    fn map_inplace<F>(&mut self, mut f: F)
+    where
+        Self: Sized,
+        F: FnMut(&mut Self::Item),
+    {
+        for i in 0..self.rows() {
+            for j in 0..self.cols() {
+                unsafe { f(self.get_mut_unchecked(i, j)) }
+            }
+        }
+    }
+    /// Combine the elements of `self` and `rhs` at the same position by `f`
+    ///
+    /// Fails with [`MatError::InconsistentDimension`] if the dimensions differ
+    ///
+    /// Trait objects can't call this, call it on their `view()` instead
+    fn zip_with<S, U, F>(&self, rhs: &dyn Mat<Item = S>, mut f: F) -> Result<DataMatrix<U>, MatError>
+    where
+        Self: Sized,
+        S: LinearElem,
+        U: LinearElem,
+        F: FnMut(&Self::Item, &S) -> U,
+    {
+        if self.dimensions() != rhs.dimensions() {
+            return Err(InconsistentDimension { need: self.dimensions(), got: rhs.dimensions() });
+        }
+        let data = self.iter().zip(Iter::new(rhs)).map(|(x, y)| f(x, y)).collect();
+        unsafe { Ok(DataMatrix::new_unchecked(data, self.rows(), self.cols())) }
+    }
+    /// Element-wise product, or [`MatError::InconsistentDimension`] if the dimensions differ
+    ///
+    /// Trait objects can't call this, call it on their `view()` instead
+    fn hadamard(&self, rhs: &dyn Mat<Item = Self::Item>) -> Result<DataMatrix<Self::Item>, MatError>
+    where
+        Self: Sized,
+    {
+        self.zip_with(rhs, |x, y| x.clone() * y)
+    }
+    /// Element-wise division, that is `self[ij] * rhs[ij].inv()`
+    ///
+    /// Dividing by zero is up to `inv()` of the element
+    ///
+    /// Trait objects can't call this, call it on their `view()` instead
+    fn hadamard_div(&self, rhs: &dyn Mat<Item = Self::Item>) -> Result<DataMatrix<Self::Item>, MatError>
+    where
+        Self: Sized,
+        Self::Item: Inv,
+    {
+        self.zip_with(rhs, |x, y| x.clone() * &y.clone().inv())
+    }
+
     /// Same as `col`
     fn row(&self, i: usize) -> Result<SliceMatrix<Self::Item>, MatError>
     where
@@ -319,7 +463,9 @@ pub trait Mat {
 
     /// Do the Gussian Elimination and get the [`EliminatedMatrix`] with pivot information
     /// for further use
-    fn eliminated(self) -> EliminatedMatrix<Self::Item, Self>
+    ///
+    /// Fails with [`MatError::Overflow`] if the elements overflow during elimination
+    fn eliminated(self) -> Result<EliminatedMatrix<Self::Item, Self>, MatError>
     where
         Self: Sized,
         Self::Item: Inv,
@@ -344,6 +490,12 @@ pub trait Mat {
         Ok(())
     }
 
+    /// `Some(self)` if `self` is a [`DataMatrix`], so that algorithms can work on its raw data,
+    /// for example across threads. Returns [`None`] by default
+    fn as_data_matrix_mut(&mut self) -> Option<&mut DataMatrix<Self::Item>> {
+        None
+    }
+
     /// Clone the elements on the diagnol and return them in a [`Vec`]
     /// If the matrix is not square, all elements at `(i, i)` are taken
     fn clone_diag(&self) -> Vec<Self::Item> {
@@ -359,6 +511,45 @@ pub trait Mat {
     }
 }
 
+impl<'a, T: LinearElem> dyn Mat<Item = T> + 'a {
+    /// A [`SliceMatrix`] of all of `self`, so that a trait object can call the methods of
+    /// [`Mat`] that need `Self: Sized`, such as [`Mat::map`] and [`Mat::zip_with`]
+    pub fn view(&self) -> SliceMatrix<'_, T> {
+        unsafe { SliceMatrix::new_unchecked(self, 0, self.rows(), 0, self.cols()) }
+    }
+}
+
+/// Same as [`Mat::dot_unchecked`], but reports overflow
+pub(crate) unsafe fn checked_dot<T: LinearElem>(a: &DataMatrix<T>, b: &dyn Mat<Item = T>) -> Result<DataMatrix<T>, MatError> {
+    let (m, l, n) = (a.rows(), a.cols(), b.cols());
+    let mut result: DataMatrix<T> = DataMatrix::zeros(m, n);
+    for i in 0..m {
+        for k in 0..l {
+            let aik = a.get_unchecked(i, k);
+            if aik.is_add_zero() {
+                continue;
+            }
+            for j in 0..n {
+                let x = aik.checked_mul(b.get_unchecked(k, j))
+                    .and_then(|x| result.get_unchecked(i, j).checked_add(&x))
+                    .ok_or(MatError::Overflow)?;
+                *result.get_mut_unchecked(i, j) = x;
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Check that a matrix of dimension `got` can be repeated to `need`, that is, its rows are
+/// either `1` or the same, and so are its cols
+fn check_broadcast(need: (usize, usize), got: (usize, usize)) -> Result<(), MatError> {
+    if (got.0 == 1 || got.0 == need.0) && (got.1 == 1 || got.1 == need.1) {
+        Ok(())
+    } else {
+        Err(InconsistentDimension { need, got })
+    }
+}
+
 /// Implements how two trait object of [`Mat`] are equaled
 impl<T> PartialEq<&dyn Mat<Item = T>> for &dyn Mat<Item = T>
 where
@@ -7,6 +7,7 @@ mod inv_mat {
     ///
     /// If `mat` is not square, of course there is a [`MatError::DimensionError`];
     /// And if the matrix is not invertable, returns a [`MatError::NotInvertable`] containing the rank;
+    /// If the elements overflow during elimination, returns a [`MatError::Overflow`];
     ///
     /// Caution: this method ruins the original matrix, turning it into an identity
     pub fn inv<T>(mat: &mut dyn Mat<Item = T>) -> Result<DataMatrix<T>, MatError>
@@ -27,8 +28,8 @@ mod inv_mat {
         ]
         .unwrap();
 
-        let mut augmented = EliminatedMatrix::eliminated(augmented);
-        augmented.reduce();
+        let mut augmented = EliminatedMatrix::eliminated(augmented)?;
+        augmented.reduce()?;
 
         if augmented.pivot_cols.last().unwrap().unwrap() >= augmented.rows() {
             let rank = augmented
@@ -51,7 +52,7 @@ mod inv_mat {
         fn test_inv() {
             use crate::alg;
             use crate::DataMatrix;
-            use crate::Rational;
+            use crate::{rational, Rational};
             use mat_macro::mat_;
 
             let mut a: DataMatrix<Rational> = mat_![
@@ -64,17 +65,55 @@ mod inv_mat {
             assert_eq!(
                 inv,
                 mat_![
-                    (Rational(-1, 5)) (Rational(2, 5));
-                    (Rational(3, 5)) (Rational(-1, 5));
+                    (rational!(-1, 5)) (rational!(2, 5));
+                    (rational!(3, 5)) (rational!(-1, 5));
                 ]
             );
         }
+
+        #[test]
+        #[cfg(feature = "big_rational")]
+        fn test_inv_hilbert() {
+            use crate::alg;
+            use crate::{rational, DataMatrix, Mat};
+
+            // Entries of the inverse of a Hilbert matrix grow fast enough to overflow `i64`
+            let n = 10;
+            let data = (0..n * n).map(|k| rational!(1, (k / n + k % n + 1) as i64)).collect();
+            let h = DataMatrix::new(data, n, n).unwrap();
+            let inv = alg::inv(&mut h.clone_data()).unwrap();
+
+            assert_eq!(*inv.get(0, 0).unwrap(), rational!(100, 1));
+            assert_eq!(inv.dot(&h).unwrap(), DataMatrix::identity(n));
+        }
     }
 }
 pub use inv_mat::inv;
 
+mod lu;
+pub use lu::{lu, LUDecomposition};
+
+/// LU decomposition of sparse matrices
+mod sparse_lu;
+pub use sparse_lu::SparseLUDecomposition;
+
+mod cholesky;
+pub use cholesky::{cholesky, cholesky_unchecked, ldl, ldl_unchecked};
+
+mod charpoly;
+pub use charpoly::{charpoly, charpoly_unchecked};
+
+/// Matrix polynomials
+mod minpoly;
+pub use minpoly::{eval_poly, minimal_polynomial};
+
+/// Jordan normal form
+mod jordan;
+pub use jordan::jordan_form;
+
 mod det_mat;
-pub use det_mat::det;
+pub use det_mat::{det, det_bareiss, det_bareiss_unchecked, det_lu, det_lu_unchecked};
+pub use det_mat::{det_permutation, det_permutation_unchecked, DetElem};
 
 mod trace {
     use crate::element::LinearElem;
@@ -112,10 +151,38 @@ pub use trace::{trace, trace_unchecked};
 mod linear_equation;
 pub use linear_equation::{solve, solve_augmented, SolveResult};
 
+/// Kronecker product, direct sum and vectorization
+mod kron;
+pub use kron::{direct_sum, kron, unvec, vec};
+
+/// Normal forms over the integers
+mod integer;
+pub use integer::{hermite_normal_form, smith_normal_form, solve_integer};
+
 /// A module for decompositing matrix into upper triangle
 mod eigen;
 pub use eigen::{hessenberg_unchecked, hessengerb, householder_unchecked, householder, qr_unchecked, qr};
-pub use eigen::EigenValueSolver;
+pub use eigen::{ConvergenceReport, EigenElem, EigenValueSolver};
+
+/// Eigen vectors by inverse iteration
+mod eigvec;
+pub use eigvec::eigenvectors;
+
+/// Jacobi eigen solver for symmetric matrices
+mod jacobi;
+pub use jacobi::jacobi_eigen;
+
+/// Complex Schur decomposition
+mod schur;
+pub use schur::schur;
+
+/// Matrix exponential, square root and logarithm
+mod funm;
+pub use funm::{expm, logm, sqrtm};
+
+/// Singular value decomposition
+mod svd;
+pub use svd::svd;
 
 /// A module for calculating normal of vectors
 mod normal;
@@ -0,0 +1,139 @@
+use super::super::eliminated_matrix::{checked_add, checked_mul};
+use crate::element::LinearElem;
+use crate::error::MatError;
+use crate::matrix::Mat;
+use crate::Polynomial;
+use MatError::*;
+
+/// Same as [`charpoly`], without checking dimension
+///
+/// # Safety
+/// `mat` must be a square
+pub unsafe fn charpoly_unchecked<T>(mat: &dyn Mat<Item = T>) -> Result<Polynomial<T>, MatError>
+where
+    T: LinearElem,
+{
+    let n = mat.rows();
+    let neg = |x: &T| T::add_zero() - x;
+
+    // characteristic polynomial of the leading `(k, k)` block, from high to low
+    let mut p = vec![T::mul_zero()];
+    for k in 0..n {
+        // with the leading `(k + 1, k + 1)` block being `[A c; r a]`, `p` is multiplied by the
+        // lower triangle toeplitz matrix of `[1, -a, -rc, -rAc, -rA^2c, ...]`
+        let mut t = Vec::with_capacity(k + 2);
+        t.push(T::mul_zero());
+        t.push(neg(mat.get_unchecked(k, k)));
+
+        let mut v: Vec<T> = (0..k).map(|i| mat.get_unchecked(i, k).clone()).collect();
+        for step in 0..k {
+            let mut rv = T::add_zero();
+            for (j, vj) in v.iter().enumerate() {
+                rv = checked_add(&rv, &checked_mul(mat.get_unchecked(k, j), vj)?)?;
+            }
+            t.push(neg(&rv));
+
+            if step + 1 < k {
+                let mut av = Vec::with_capacity(k);
+                for i in 0..k {
+                    let mut x = T::add_zero();
+                    for (j, vj) in v.iter().enumerate() {
+                        x = checked_add(&x, &checked_mul(mat.get_unchecked(i, j), vj)?)?;
+                    }
+                    av.push(x);
+                }
+                v = av;
+            }
+        }
+
+        let mut next = Vec::with_capacity(k + 2);
+        for i in 0..k + 2 {
+            let mut x = T::add_zero();
+            for (j, pj) in p.iter().enumerate().take(i + 1) {
+                x = checked_add(&x, &checked_mul(&t[i - j], pj)?)?;
+            }
+            next.push(x);
+        }
+        p = next;
+    }
+
+    p.reverse();
+    Ok(Polynomial { coef: p })
+}
+
+/// The characteristic polynomial `det(xI - mat)` of a square `mat`, which is monic
+///
+/// Coefficients are stored from low to high. This is Berkowitz's algorithm, which takes no
+/// divisions, so the result is exact for [`Rational`](crate::Rational) and integers. Fails with
+/// [`MatError::Overflow`] if intermediate results overflow
+pub fn charpoly<T>(mat: &dyn Mat<Item = T>) -> Result<Polynomial<T>, MatError>
+where
+    T: LinearElem,
+{
+    if !mat.is_square() {
+        return Err(NotSquare {
+            dim: mat.dimensions(),
+        });
+    }
+    unsafe { charpoly_unchecked(mat) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{rational, Complex, DataMatrix, Rational};
+    use mat_macro::mat_;
+
+    #[test]
+    fn test_charpoly() {
+        let a: DataMatrix<Rational> = mat_![
+            2 1 0;
+            1 2 1;
+            0 1 2;
+        ]
+        .convert();
+        // x^3 - 6x^2 + 10x - 4
+        assert_eq!(
+            charpoly(&a).unwrap().coef,
+            vec![rational!(-4, 1), rational!(10, 1), rational!(-6, 1), rational!(1, 1)]
+        );
+
+        // `(x-1)(x-2)(x-3)(x-4)(x^2+1)`
+        let a: DataMatrix<Rational> = mat_![
+            10 (-36) 60 (-59) 50 (-24);
+            1 0 0 0 0 0;
+            0 1 0 0 0 0;
+            0 0 1 0 0 0;
+            0 0 0 1 0 0;
+            0 0 0 0 1 0;
+        ]
+        .convert();
+        let a = a.transposed();
+        assert_eq!(
+            charpoly(&a).unwrap().coef,
+            [24, -50, 59, -60, 36, -10, 1].iter().map(|&x| rational!(x, 1)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_charpoly_integer() {
+        let a: DataMatrix<i64> = mat_![
+            1 2;
+            3 4;
+        ];
+        assert_eq!(charpoly(&a).unwrap().coef, vec![-2, -5, 1]);
+    }
+
+    #[test]
+    fn test_charpoly_complex() {
+        // `x^2 - 2ix - 1`
+        let a = mat_![
+            (Complex(1.0, 1.0)) (Complex(1.0, 0.0));
+            (Complex(-1.0, 0.0)) (Complex(-1.0, 1.0));
+        ];
+        let p = charpoly(&a).unwrap();
+        for (x, y) in p.coef.iter().zip([Complex(-1.0, 0.0), Complex(0.0, -2.0), Complex(1.0, 0.0)]) {
+            assert!((*x - &y).normal() < 1e-12, "{}", p);
+        }
+    }
+}
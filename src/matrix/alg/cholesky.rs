@@ -0,0 +1,232 @@
+use super::super::eliminated_matrix::{checked_mul, checked_sub};
+use crate::element::{FloatElem, Inv, LinearElem};
+use crate::error::MatError;
+use crate::matrix::{DataMatrix, Mat};
+use MatError::*;
+
+fn check_square<T: LinearElem>(mat: &dyn Mat<Item = T>) -> Result<(), MatError> {
+    if mat.dimensions() == (0, 0) {
+        return Err(EmptyMatrix);
+    }
+    if !mat.is_square() {
+        return Err(NotSquare {
+            dim: mat.dimensions(),
+        });
+    }
+    Ok(())
+}
+
+/// Cholesky decomposition of a hermitian positive definite `mat`, returning the lower triangle
+/// `L` where `mat = L * L^H`
+///
+/// Only the lower triangle of `mat` is read, the upper one is assumed to be its conjugate
+///
+/// # Safety
+/// `mat` must be a non-empty square
+pub unsafe fn cholesky_unchecked<T>(mat: &dyn Mat<Item = T>) -> Result<DataMatrix<T>, MatError>
+where
+    T: FloatElem,
+{
+    let n = mat.rows();
+    let mut l: DataMatrix<T> = DataMatrix::zeros(n, n);
+
+    for j in 0..n {
+        let mut d = mat.get_unchecked(j, j).re();
+        for k in 0..j {
+            d -= l.get_unchecked(j, k).normal().powi(2);
+        }
+        // also fails on NaN
+        if d.partial_cmp(&0.0) != Some(std::cmp::Ordering::Greater) {
+            return Err(NotPositiveDefinite);
+        }
+        let ljj = T::from_f64(d.sqrt());
+        let ljj_inv = ljj.clone().inv();
+        *l.get_mut_unchecked(j, j) = ljj;
+
+        for i in j + 1..n {
+            let mut s = mat.get_unchecked(i, j).clone();
+            for k in 0..j {
+                s -= &(l.get_unchecked(i, k).clone() * &l.get_unchecked(j, k).conj());
+            }
+            *l.get_mut_unchecked(i, j) = s * &ljj_inv;
+        }
+    }
+
+    Ok(l)
+}
+
+/// Check dimensions then call [`cholesky_unchecked`]
+///
+/// If the decomposition breaks down, returns a [`MatError::NotPositiveDefinite`], so this also
+/// serves as a test of definiteness
+pub fn cholesky<T>(mat: &dyn Mat<Item = T>) -> Result<DataMatrix<T>, MatError>
+where
+    T: FloatElem,
+{
+    check_square(mat)?;
+    unsafe { cholesky_unchecked(mat) }
+}
+
+/// LDL^T decomposition of a symmetric `mat`, returning `(L, D)` where `L` is unit-lower
+/// triangular and `D` is diagnol
+///
+/// No square roots are taken, so the decomposition is exact for [`Rational`](crate::Rational);
+/// `mat` is positive definite iff all elements of `D` are positive.
+///
+/// Only the lower triangle of `mat` is read. There is no pivoting, so if a zero pivot has to be
+/// used, a [`MatError::NotPositiveDefinite`] is returned
+///
+/// # Safety
+/// `mat` must be a non-empty square
+pub unsafe fn ldl_unchecked<T>(
+    mat: &dyn Mat<Item = T>,
+) -> Result<(DataMatrix<T>, DataMatrix<T>), MatError>
+where
+    T: LinearElem + Inv,
+{
+    let n = mat.rows();
+    let mut l: DataMatrix<T> = DataMatrix::identity(n);
+    let mut d: Vec<T> = Vec::with_capacity(n);
+
+    for j in 0..n {
+        let mut dj = mat.get_unchecked(j, j).clone();
+        for (k, dk) in d.iter().enumerate() {
+            let lk = l.get_unchecked(j, k);
+            dj = checked_sub(&dj, &checked_mul(&checked_mul(lk, lk)?, dk)?)?;
+        }
+
+        for i in j + 1..n {
+            let mut s = mat.get_unchecked(i, j).clone();
+            for (k, dk) in d.iter().enumerate() {
+                s = checked_sub(
+                    &s,
+                    &checked_mul(&checked_mul(l.get_unchecked(i, k), l.get_unchecked(j, k))?, dk)?,
+                )?;
+            }
+            // zeros are tested exactly, as `is_add_zero` has a tolerance for floats
+            if dj == T::add_zero() {
+                // the column can be left zero only if there is nothing to eliminate
+                if s != T::add_zero() {
+                    return Err(NotPositiveDefinite);
+                }
+                continue;
+            }
+            *l.get_mut_unchecked(i, j) = checked_mul(&s, &dj.clone().inv())?;
+        }
+
+        d.push(dj);
+    }
+
+    Ok((l, DataMatrix::with_diag(d)))
+}
+
+/// Check dimensions then call [`ldl_unchecked`]
+pub fn ldl<T>(mat: &dyn Mat<Item = T>) -> Result<(DataMatrix<T>, DataMatrix<T>), MatError>
+where
+    T: LinearElem + Inv,
+{
+    check_square(mat)?;
+    unsafe { ldl_unchecked(mat) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{rational, Complex, Rational};
+    use mat_macro::mat_;
+
+    #[test]
+    fn test_cholesky() {
+        let a: DataMatrix<f64> = mat_![
+            4.0   12.0  -16.0;
+            12.0  37.0  -43.0;
+            -16.0 -43.0  98.0;
+        ];
+        let l = cholesky(&a).unwrap();
+        assert_eq!(
+            l,
+            mat_![
+                2.0  0.0 0.0;
+                6.0  1.0 0.0;
+                -8.0 5.0 3.0;
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cholesky_complex() {
+        let a: DataMatrix<Complex> = mat_![
+            (Complex(2.0, 0.0)) (Complex(1.0, -1.0));
+            (Complex(1.0, 1.0)) (Complex(3.0, 0.0));
+        ];
+        let l = cholesky(&a).unwrap();
+        let mut lh: DataMatrix<Complex> = DataMatrix::zeros(2, 2);
+        for i in 0..2 {
+            for j in 0..2 {
+                *lh.get_mut(i, j).unwrap() = l.get(j, i).unwrap().adjoint();
+            }
+        }
+        let llh = l.dot(&lh).unwrap();
+        for i in 0..2 {
+            for j in 0..2 {
+                assert!((*llh.get(i, j).unwrap() - a.get(i, j).unwrap()).normal() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_not_positive_definite() {
+        let a: DataMatrix<f64> = mat_![
+            1.0 2.0;
+            2.0 1.0;
+        ];
+        assert!(matches!(cholesky(&a), Err(MatError::NotPositiveDefinite)));
+    }
+
+    #[test]
+    fn test_ldl() {
+        let a: DataMatrix<Rational> = mat_![
+            4   12  -16;
+            12  37  -43;
+            -16 -43  98;
+        ]
+        .convert();
+        let (l, d) = ldl(&a).unwrap();
+        assert_eq!(
+            l,
+            mat_![
+                1  0 0;
+                3  1 0;
+                -4 5 1;
+            ]
+            .convert()
+        );
+        assert_eq!(d, DataMatrix::with_diag(vec![rational!(4, 1), rational!(1, 1), rational!(9, 1)]));
+
+        // indefinite, but still decomposable
+        let a: DataMatrix<Rational> = mat_![
+            1 2;
+            2 1;
+        ]
+        .convert();
+        let (l, d) = ldl(&a).unwrap();
+        assert_eq!(*d.get(1, 1).unwrap(), rational!(-3, 1));
+        let mut lt = l.clone_data();
+        lt.transpose();
+        assert_eq!(l.dot(&d).unwrap().dot(&lt).unwrap(), a);
+    }
+
+    #[test]
+    fn test_ldl_scaled() {
+        // pivots far below the tolerance of `is_add_zero`, a power of two so that it's exact
+        let s = 2f64.powi(-24);
+        let a: DataMatrix<f64> = mat_![
+            (4.0 * s)   (12.0 * s)  (-16.0 * s);
+            (12.0 * s)  (37.0 * s)  (-43.0 * s);
+            (-16.0 * s) (-43.0 * s) (98.0 * s);
+        ];
+        let (l, d) = ldl(&a).unwrap();
+        assert_eq!(l, mat_![1.0 0.0 0.0; 3.0 1.0 0.0; (-4.0) 5.0 1.0;]);
+        assert_eq!(d, DataMatrix::with_diag(vec![4.0 * s, s, 9.0 * s]));
+    }
+}
@@ -1,5 +1,7 @@
 use super::super::{Mat, MatError};
-use crate::element::LinearElem;
+use super::LUDecomposition;
+use crate::element::{Inv, LinearElem, Normal};
+use std::ops::Div;
 use MatError::*;
 
 /// Encapsules the logic for ennumerating all permutations, as well as the symbol of the permutation
@@ -69,7 +71,67 @@ impl Iterator for Permutation {
     }
 }
 
-pub fn det<T>(mat: &dyn Mat<Item = T>) -> Result<T, MatError>
+/// Species how the determinant of a matrix of `Self` is calculated
+///
+/// The default is expanding over all permutations, which takes O(n!) but works for any ring;
+/// element types with division should use [`det_bareiss`] or [`det_lu`] instead
+pub trait DetElem: LinearElem {
+    /// Determinant of `mat`
+    ///
+    /// # Safety
+    /// `mat` must be a non-empty square
+    unsafe fn det_unchecked(mat: &dyn Mat<Item = Self>) -> Result<Self, MatError> {
+        Ok(det_permutation_unchecked(mat))
+    }
+}
+
+mod implementations {
+    use super::*;
+    use crate::{Complex, MatBlock, Polynomial, Rational};
+
+    impl DetElem for Rational {
+        unsafe fn det_unchecked(mat: &dyn Mat<Item = Self>) -> Result<Self, MatError> {
+            det_bareiss_unchecked(mat)
+        }
+    }
+    impl DetElem for i32 {
+        unsafe fn det_unchecked(mat: &dyn Mat<Item = Self>) -> Result<Self, MatError> {
+            det_bareiss_unchecked(mat)
+        }
+    }
+    impl DetElem for i64 {
+        unsafe fn det_unchecked(mat: &dyn Mat<Item = Self>) -> Result<Self, MatError> {
+            det_bareiss_unchecked(mat)
+        }
+    }
+    impl DetElem for f64 {
+        unsafe fn det_unchecked(mat: &dyn Mat<Item = Self>) -> Result<Self, MatError> {
+            det_lu_unchecked(mat)
+        }
+    }
+    impl DetElem for f32 {
+        unsafe fn det_unchecked(mat: &dyn Mat<Item = Self>) -> Result<Self, MatError> {
+            det_lu_unchecked(mat)
+        }
+    }
+    impl DetElem for Complex {
+        unsafe fn det_unchecked(mat: &dyn Mat<Item = Self>) -> Result<Self, MatError> {
+            det_lu_unchecked(mat)
+        }
+    }
+    #[cfg(feature = "big_rational")]
+    impl DetElem for num_bigint::BigInt {
+        unsafe fn det_unchecked(mat: &dyn Mat<Item = Self>) -> Result<Self, MatError> {
+            det_bareiss_unchecked(mat)
+        }
+    }
+
+    // no division, so these expand over all permutations
+    impl<T: LinearElem> DetElem for Polynomial<T> {}
+    impl<'a, T: LinearElem + 'a> DetElem for MatBlock<'a, T> {}
+}
+
+fn check_det_dimension<T>(mat: &dyn Mat<Item = T>) -> Result<(), MatError>
 where
     T: LinearElem,
 {
@@ -83,34 +145,142 @@ where
         return Err(EmptyMatrix);
     }
 
+    Ok(())
+}
+
+fn neg<T: LinearElem>(x: T) -> T {
+    T::add_zero() - &x
+}
+
+/// Calculate the determinant of `mat`, using the method [`DetElem`] picks for `T`
+///
+/// Fails with [`MatError::Overflow`] if intermediate results overflow
+pub fn det<T>(mat: &dyn Mat<Item = T>) -> Result<T, MatError>
+where
+    T: DetElem,
+{
+    check_det_dimension(mat)?;
+    unsafe { T::det_unchecked(mat) }
+}
+
+/// Determinant by expanding over all permutations, taking O(n!)
+///
+/// # Safety
+/// `mat` must be a non-empty square
+pub unsafe fn det_permutation_unchecked<T>(mat: &dyn Mat<Item = T>) -> T
+where
+    T: LinearElem,
+{
     let mut perm = Permutation::new(mat.rows());
 
     let mut result = T::add_zero();
 
-    unsafe {
-        loop {
-            let mut tmp = if perm.neg() {
-                let mut t = T::add_zero();
-                t.sub_assign(&T::mul_zero());
-                t
-            } else {
-                T::mul_zero()
-            };
+    loop {
+        let mut tmp = if perm.neg() {
+            neg(T::mul_zero())
+        } else {
+            T::mul_zero()
+        };
 
-            for i in 0..mat.rows() {
-                let j = perm.get_unchecked(i);
-                tmp.mul_assign(mat.get_unchecked(i, j));
-            }
+        for i in 0..mat.rows() {
+            let j = perm.get_unchecked(i);
+            tmp.mul_assign(mat.get_unchecked(i, j));
+        }
+
+        result.add_assign(&tmp);
+
+        if perm.next().is_none() {
+            break;
+        }
+    }
+
+    result
+}
+
+/// Same as [`det`], but always expands over all permutations; only a ring is required of `T`
+pub fn det_permutation<T>(mat: &dyn Mat<Item = T>) -> Result<T, MatError>
+where
+    T: LinearElem,
+{
+    check_det_dimension(mat)?;
+    Ok(unsafe { det_permutation_unchecked(mat) })
+}
+
+/// Determinant by fraction-free Bareiss elimination, taking O(n^3)
+///
+/// Every division is exact, so this works for integers as well as [`Rational`](crate::Rational),
+/// and intermediate results stay as small as minors of `mat`
+///
+/// # Safety
+/// `mat` must be a non-empty square
+pub unsafe fn det_bareiss_unchecked<T>(mat: &dyn Mat<Item = T>) -> Result<T, MatError>
+where
+    T: LinearElem + for<'a> Div<&'a T, Output = T>,
+{
+    let n = mat.rows();
+    let mut a = mat.clone_data();
+    let mut negative = false;
+    let mut prev = T::mul_zero();
 
-            result.add_assign(&tmp);
+    for k in 0..n - 1 {
+        // find a non-zero pivot
+        if a.get_unchecked(k, k).is_add_zero() {
+            match (k + 1..n).find(|&i| !a.get_unchecked(i, k).is_add_zero()) {
+                None => return Ok(T::add_zero()),
+                Some(i) => {
+                    a.row_unchecked(k).swap_unchecked(&mut a.row_unchecked(i));
+                    negative = !negative;
+                }
+            }
+        }
 
-            if let None = perm.next() {
-                break;
+        for i in k + 1..n {
+            for j in k + 1..n {
+                let val = a
+                    .get_unchecked(i, j)
+                    .checked_mul(a.get_unchecked(k, k))
+                    .zip(a.get_unchecked(i, k).checked_mul(a.get_unchecked(k, j)))
+                    .and_then(|(x, y)| x.checked_sub(&y))
+                    .ok_or(Overflow)?;
+                *a.get_mut_unchecked(i, j) = val / &prev;
             }
         }
+        prev = a.get_unchecked(k, k).clone();
     }
 
-    Ok(result)
+    let result = a.get_unchecked(n - 1, n - 1).clone();
+    Ok(if negative { neg(result) } else { result })
+}
+
+/// Same as [`det`], but always uses Bareiss elimination
+pub fn det_bareiss<T>(mat: &dyn Mat<Item = T>) -> Result<T, MatError>
+where
+    T: LinearElem + for<'a> Div<&'a T, Output = T>,
+{
+    check_det_dimension(mat)?;
+    unsafe { det_bareiss_unchecked(mat) }
+}
+
+/// Determinant by LU decomposition with partial pivoting, taking O(n^3)
+///
+/// Pivots are chosen by the largest [`Normal`], which keeps floating point error small
+///
+/// # Safety
+/// `mat` must be a non-empty square
+pub unsafe fn det_lu_unchecked<T>(mat: &dyn Mat<Item = T>) -> Result<T, MatError>
+where
+    T: LinearElem + Inv + Normal,
+{
+    LUDecomposition::new_unchecked(mat)?.det()
+}
+
+/// Same as [`det`], but always uses LU decomposition
+pub fn det_lu<T>(mat: &dyn Mat<Item = T>) -> Result<T, MatError>
+where
+    T: LinearElem + Inv + Normal,
+{
+    check_det_dimension(mat)?;
+    unsafe { det_lu_unchecked(mat) }
 }
 
 #[cfg(test)]
@@ -157,5 +327,81 @@ mod test {
         ];
 
         assert_eq!(det(&m).unwrap(), -4);
+        assert_eq!(det_permutation(&m).unwrap(), -4);
+    }
+
+    #[test]
+    fn test_det_rational() {
+        use crate::{rational, Rational};
+
+        let m: DataMatrix<Rational> = mat_![
+            (rational!(1, 2)) (rational!(1, 3)) (rational!(1, 1));
+            (rational!(2, 3)) (rational!(0, 1)) (rational!(-1, 4));
+            (rational!(3, 1)) (rational!(5, 7)) (rational!(2, 1));
+        ];
+
+        assert_eq!(det(&m).unwrap(), det_permutation(&m).unwrap());
+        assert_eq!(det(&m).unwrap(), rational!(-65, 504));
+    }
+
+    #[test]
+    fn test_det_large() {
+        use crate::Rational;
+
+        // `min(i, j) + 1` has determinant 1; expanding 12! permutations would never finish
+        let n = 12;
+        let data = (0..n * n).map(|k| Rational::from((k / n).min(k % n) as i32 + 1)).collect();
+        let m = DataMatrix::new(data, n, n).unwrap();
+        assert_eq!(det(&m).unwrap(), Rational::from(1));
+
+        let m: DataMatrix<f64> = m.convert();
+        assert!((det(&m).unwrap() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_det_lu() {
+        let m: DataMatrix<f64> = mat_![
+            0.0 1.0 2.0;
+            0.0 3.0 4.0;
+            2.0 8.0 9.0;
+        ];
+        assert!((det(&m).unwrap() + 4.0).abs() < 1e-9);
+
+        let singular: DataMatrix<f64> = mat_![
+            1.0 2.0;
+            2.0 4.0;
+        ];
+        assert!(det(&singular).unwrap().abs() < 1e-9);
+
+        use crate::Complex;
+        let m: DataMatrix<Complex> = mat_![
+            (Complex(1.0, 1.0)) (Complex(2.0, 0.0));
+            (Complex(0.0, -1.0)) (Complex(3.0, 2.0));
+        ];
+        // (1+i)(3+2i) - 2(-i) = 1 + 7i
+        let d = det(&m).unwrap();
+        assert!((d - &Complex(1.0, 7.0)).normal() < 1e-9);
+    }
+
+    #[test]
+    fn test_det_poly() {
+        use crate::polynomial;
+        // characteristic polynomial of [1 2; 3 4]
+        let m = DataMatrix::new(
+            vec![polynomial!(1, -1), polynomial!(2), polynomial!(3), polynomial!(4, -1)],
+            2,
+            2,
+        )
+        .unwrap();
+        assert_eq!(det(&m).unwrap(), polynomial!(-2, -5, 1));
+    }
+
+    #[test]
+    fn test_det_overflow() {
+        let m: DataMatrix<i32> = mat_![
+            (i32::MAX) 1;
+            1 (i32::MAX);
+        ];
+        assert!(matches!(det(&m), Err(MatError::Overflow)));
     }
 }
@@ -1,34 +1,53 @@
 use crate::Complex;
 use crate::{matrix::Mat, DataMatrix, error::MatError, SliceMatrix};
-use crate::element::AddZero;
+use crate::matrix::for_each_row_block;
+use crate::element::FloatElem;
 
 use super::{col_normal_unchecked, col_normal_sqr_unchecked};
+use std::ops::Range;
 
 
+/// Conjugate transpose of `m`
+pub(crate) fn adjoint<T: FloatElem>(m: &dyn Mat<Item = T>) -> DataMatrix<T> {
+    let mut r = DataMatrix::zeros(m.cols(), m.rows());
+    for i in 0..m.rows() { for j in 0..m.cols() {
+        unsafe { *r.get_mut_unchecked(j, i) = m.get_unchecked(i, j).conj(); }
+    }}
+    r
+}
+
+/// `x / |x|`, or `1` if `x` is zero
+pub(crate) fn phase<T: FloatElem>(x: &T) -> T {
+    let n = x.normal();
+    if n == 0.0 { T::mul_zero() } else { x.clone() * &T::from_f64(n.recip()) }
+}
+
 /// Calculate the Householder Matrix of a column vector.
 /// However, the dimension of the input vector is not checked,
 /// so the first column is taken.
-/// 
+///
 /// # Householder Matrix:
 /// Given a vector `v`, the corresponding Householder Matrix `A`
 /// can reduce all elements of `v` except `v[0]` to zero.
-pub unsafe fn householder_unchecked(v: &dyn Mat<Item=f64>) -> DataMatrix<f64> {
+///
+/// For complex vectors the reflector is `I - 2ww^H/(w^Hw)`, which is both hermitian and unitary
+pub unsafe fn householder_unchecked<T: FloatElem>(v: &dyn Mat<Item = T>) -> DataMatrix<T> {
     let dim = v.rows();
-    
-    let c = -v.get_unchecked(0, 0).signum() * col_normal_unchecked(v, 0);
+
+    let c = T::add_zero() - &(phase(v.get_unchecked(0, 0)) * &T::from_f64(col_normal_unchecked(v, 0)));
 
     let mut w = v.clone_data();
-    *w.get_mut_unchecked(0, 0) -= c;
+    *w.get_mut_unchecked(0, 0) -= &c;
 
     let mut h = DataMatrix::identity(dim);
     h.sub_assign(
-        w.dot_unchecked(&w.clone_data().transposed())
-        .scale(&(2.0 / col_normal_sqr_unchecked(&w, 0)))
+        w.dot_unchecked(&adjoint(&w))
+        .scale(&T::from_f64(2.0 / col_normal_sqr_unchecked(&w, 0)))
     );
     h
 }
 
-pub fn householder(v: &dyn Mat<Item = f64>) -> Result<DataMatrix<f64>, MatError> {
+pub fn householder<T: FloatElem>(v: &dyn Mat<Item = T>) -> Result<DataMatrix<T>, MatError> {
     if v.cols() != 1 {
         return Err(MatError::ExpectedVector);
     }
@@ -40,14 +59,20 @@ pub fn householder(v: &dyn Mat<Item = f64>) -> Result<DataMatrix<f64>, MatError>
 
 /// Calculate the Hessenberg Matrix of `m`, where all elements `m[i, j]` satisfying
 /// `i >= j + 2` are zeros, while not altering `m`'s eigen values
-pub unsafe fn hessenberg_unchecked(m: &mut dyn Mat<Item = f64>) {
+pub unsafe fn hessenberg_unchecked<T: FloatElem>(m: &mut dyn Mat<Item = T>) {
+    hessenberg_acc_unchecked(m, None);
+}
+
+/// Same as [`hessenberg_unchecked`], but also right multiplies `q` by each reflection, so that
+/// starting from the identity, `q` ends up unitary with `A = q m q^H` for the original `A`
+pub(crate) unsafe fn hessenberg_acc_unchecked<T: FloatElem>(m: &mut dyn Mat<Item = T>, mut q: Option<&mut DataMatrix<T>>) {
     let n = m.rows();
 
-    for k in 1..n - 1 {
+    for k in 1..n.saturating_sub(1) {
         let v = SliceMatrix::new_unchecked(m, k, n - k, k - 1, 1);
 
         // If `v` is zero, skip the transoformation of this slice
-        if col_normal_sqr_unchecked(&v, 0).is_add_zero() { continue; }
+        if col_normal_sqr_unchecked(&v, 0) == 0.0 { continue; }
 
         let h = householder_unchecked(&v);
 
@@ -62,32 +87,42 @@ pub unsafe fn hessenberg_unchecked(m: &mut dyn Mat<Item = f64>) {
         b.write_data_unchecked(&bh);
         c.write_data_unchecked(&hc);
         d.write_data_unchecked(&hdh);
+        for i in k + 1..n {
+            *m.get_mut_unchecked(i, k - 1) = T::add_zero();
+        }
 
+        if let Some(q) = q.as_deref_mut() {
+            let mut e = SliceMatrix::new_unchecked(q, 0, n, k, n - k);
+            let eh = e.dot_unchecked(&h);
+            e.write_data_unchecked(&eh);
+        }
    }
 }
 
 /// Check if m is square than call `hessengerb_unchecked`
-pub fn hessengerb(m: &mut dyn Mat<Item = f64>) -> Result<(), MatError> {
+pub fn hessengerb<T: FloatElem>(m: &mut dyn Mat<Item = T>) -> Result<(), MatError> {
     if m.dimensions() == (0, 0) {
         return Err(MatError::EmptyMatrix);
     }
     if !m.is_square() { return Err(MatError::NotSquare { dim: m.dimensions() }); }
-    
+
     unsafe { hessenberg_unchecked(m) };
     Ok(())
 }
 
-/// Perform the QR dicomposition: 
+/// Perform the QR dicomposition:
 /// Transform `m` to `R` and return `Q`
-/// Where `R` is upper-triangle and `Q` is othogonal
-pub unsafe fn qr_unchecked(m: &mut dyn Mat<Item = f64>) -> DataMatrix<f64> {
+/// Where `R` is upper-triangle and `Q` is othogonal (unitary for complex matrices)
+///
+/// With the `parallel` feature, the Householder reflections are applied across threads
+pub unsafe fn qr_unchecked<T: FloatElem>(m: &mut dyn Mat<Item = T>) -> DataMatrix<T> {
     let n = m.rows();
-    let q = DataMatrix::identity(n);
+    let mut q: DataMatrix<T> = DataMatrix::identity(n);
 
     for k in 0..n - 1 {
         let v = SliceMatrix::new_unchecked(m, k, n - k, k, 1);
 
-        if col_normal_sqr_unchecked(&v, 0).is_add_zero() { continue; }
+        if col_normal_sqr_unchecked(&v, 0) == 0.0 { continue; }
 
         let h = householder_unchecked(&v);
 
@@ -101,25 +136,27 @@ pub unsafe fn qr_unchecked(m: &mut dyn Mat<Item = f64>) -> DataMatrix<f64> {
             c.write_data_unchecked(&hc);
             d.write_data_unchecked(&hd);
         }
- 
-        {
-            let mut b = SliceMatrix::new_unchecked(&q, 0, k, k, n  -k);
-            let mut d = SliceMatrix::new_unchecked(&q, k, n - k, k, n - k);
-
-            let bh = b.dot_unchecked(&h);
-            let dh = d.dot_unchecked(&h);
 
-            b.write_data_unchecked(&bh);
-            d.write_data_unchecked(&dh);
-        }
-        
+        // `Q H`, where `H` only touches cols `k..n`, row by row
+        for_each_row_block(&mut q, |mut block| {
+            for (_, row) in block.rows_mut() {
+                let x = row[k..].to_vec();
+                for (j, y) in row[k..].iter_mut().enumerate() {
+                    let mut s = T::add_zero();
+                    for (t, xt) in x.iter().enumerate() {
+                        s += &(xt.clone() * h.get_unchecked(t, j));
+                    }
+                    *y = s;
+                }
+            }
+        });
     }
 
     q
 }
 
 /// Check the dimension of the input matrix, then call `qr_unchecked`
-pub fn qr(m: &mut dyn Mat<Item = f64>) -> Result<DataMatrix<f64>, MatError> {
+pub fn qr<T: FloatElem>(m: &mut dyn Mat<Item = T>) -> Result<DataMatrix<T>, MatError> {
     if m.dimensions() == (0, 0) { return Err(MatError::EmptyMatrix); }
     if !m.is_square() {
         return Err(MatError::NotSquare { dim: m.dimensions() });
@@ -128,25 +165,275 @@ pub fn qr(m: &mut dyn Mat<Item = f64>) -> Result<DataMatrix<f64>, MatError> {
     Ok(unsafe { qr_unchecked(m) })
 }
 
-fn eigval_2dim(a: f64, b: f64, c: f64, d: f64) -> (Complex, Complex) {
-    let re = (a + d) / 2.0;
-    let det =  a * a + d * d + 4.0 * b * c - 2.0 * a * d;
-    if det >= 0.0 {
-        (Complex(re + det.sqrt() / 2.0, 0.0), Complex(re - det.sqrt() / 2.0, 0.0))
+/// Eigen values of `[a b; c d]`
+fn eigval_2dim(a: Complex, b: Complex, c: Complex, d: Complex) -> (Complex, Complex) {
+    let half = Complex(0.5, 0.0);
+    let mean = (a + &d) * &half;
+    let diff = (a - &d) * &half;
+    let root = (diff * &diff + &(b * &c)).sqrt();
+    (mean + &root, mean - &root)
+}
+
+/// Householder vector `v` with `(I - 2vv^T/v^Tv) * x` parallel to `e1`, or [`None`] if `x` is zero
+fn householder_vec(x: &[f64]) -> Option<Vec<f64>> {
+    let norm = x.iter().map(|a| a * a).sum::<f64>().sqrt();
+    if norm == 0.0 {
+        return None;
+    }
+    let mut v = x.to_vec();
+    v[0] += if x[0] >= 0.0 { norm } else { -norm };
+    Some(v)
+}
+
+/// Apply the reflector of `v` to rows `r0..r0 + v.len()` of `m`, restricted to `cols`
+unsafe fn reflect_rows(m: &mut DataMatrix<f64>, v: &[f64], r0: usize, cols: Range<usize>) {
+    let vv: f64 = v.iter().map(|a| a * a).sum();
+    for j in cols {
+        let s: f64 = v.iter().enumerate().map(|(i, vi)| vi * m.get_unchecked(r0 + i, j)).sum();
+        let f = 2.0 * s / vv;
+        for (i, vi) in v.iter().enumerate() {
+            *m.get_mut_unchecked(r0 + i, j) -= f * vi;
+        }
+    }
+}
+
+/// Apply the reflector of `v` to cols `c0..c0 + v.len()` of `m`, restricted to `rows`
+unsafe fn reflect_cols(m: &mut DataMatrix<f64>, v: &[f64], c0: usize, rows: Range<usize>) {
+    let vv: f64 = v.iter().map(|a| a * a).sum();
+    for i in rows {
+        let s: f64 = v.iter().enumerate().map(|(j, vj)| vj * m.get_unchecked(i, c0 + j)).sum();
+        let f = 2.0 * s / vv;
+        for (j, vj) in v.iter().enumerate() {
+            *m.get_mut_unchecked(i, c0 + j) -= f * vj;
+        }
+    }
+}
+
+/// [`EigenElem::qr_step`] of [`Complex`], which also right multiplies `q` by each rotation, so
+/// that `A = q m q^H` keeps holding for the original `A`
+pub(crate) unsafe fn complex_qr_step(
+    m: &mut DataMatrix<Complex>,
+    l: usize,
+    h: usize,
+    exceptional: bool,
+    mut q: Option<&mut DataMatrix<Complex>>,
+) {
+    let n = m.rows();
+
+    let mu = if exceptional {
+        *m.get_unchecked(h, h) + &Complex(m.get_unchecked(h, h - 1).normal(), 0.0)
     } else {
-        (Complex(re, (-det).sqrt() / 2.0), Complex(re, -(-det).sqrt() / 2.0))
+        // the eigen value of the trailing 2x2 closer to `m[h, h]`
+        let d = *m.get_unchecked(h, h);
+        let (e1, e2) = eigval_2dim(
+            *m.get_unchecked(h - 1, h - 1),
+            *m.get_unchecked(h - 1, h),
+            *m.get_unchecked(h, h - 1),
+            d,
+        );
+        if (e1 - &d).normal() <= (e2 - &d).normal() { e1 } else { e2 }
+    };
+
+    for k in l..=h {
+        *m.get_mut_unchecked(k, k) -= &mu;
+    }
+
+    // `H - mu = QR`, zeroing the sub-diagnol by rotations `[c^* s^*; -s c]`
+    let mut rotations = Vec::with_capacity(h - l);
+    for k in l..h {
+        let a = *m.get_unchecked(k, k);
+        let b = *m.get_unchecked(k + 1, k);
+        let r = a.normal().hypot(b.normal());
+        let (c, s) = if r == 0.0 {
+            (Complex(1.0, 0.0), Complex(0.0, 0.0))
+        } else {
+            (a * &Complex(r.recip(), 0.0), b * &Complex(r.recip(), 0.0))
+        };
+        for j in k..n {
+            let x = *m.get_unchecked(k, j);
+            let y = *m.get_unchecked(k + 1, j);
+            *m.get_mut_unchecked(k, j) = c.adjoint() * &x + &(s.adjoint() * &y);
+            *m.get_mut_unchecked(k + 1, j) = c * &y - &(s * &x);
+        }
+        *m.get_mut_unchecked(k + 1, k) = Complex(0.0, 0.0);
+        rotations.push((c, s));
+    }
+
+    // `RQ + mu`
+    for (k, (c, s)) in (l..h).zip(rotations) {
+        for i in 0..=k + 1 {
+            let x = *m.get_unchecked(i, k);
+            let y = *m.get_unchecked(i, k + 1);
+            *m.get_mut_unchecked(i, k) = x * &c + &(y * &s);
+            *m.get_mut_unchecked(i, k + 1) = y * &c.adjoint() - &(x * &s.adjoint());
+        }
+        if let Some(q) = q.as_deref_mut() {
+            for i in 0..n {
+                let x = *q.get_unchecked(i, k);
+                let y = *q.get_unchecked(i, k + 1);
+                *q.get_mut_unchecked(i, k) = x * &c + &(y * &s);
+                *q.get_mut_unchecked(i, k + 1) = y * &c.adjoint() - &(x * &s.adjoint());
+            }
+        }
+    }
+    for k in l..=h {
+        *m.get_mut_unchecked(k, k) += &mu;
+    }
+}
+
+/// Element types [`EigenValueSolver`] can work on, specifying how a QR step is done
+pub trait EigenElem: FloatElem + Into<Complex> {
+    /// One shifted QR step on the unreduced block `l..=h` of the Hessenberg `m`
+    ///
+    /// # Safety
+    /// `l..=h` must lie in `m`, and it has at least as many rows as this step needs
+    unsafe fn qr_step(m: &mut DataMatrix<Self>, l: usize, h: usize, exceptional: bool);
+    /// Try to finish the unreduced `2x2` block at `h - 1..=h`, returning if it's done; otherwise
+    /// [`qr_step`](EigenElem::qr_step) is run on it
+    ///
+    /// # Safety
+    /// `h` must lie in `m` and be at least `1`
+    unsafe fn split_2x2(m: &mut DataMatrix<Self>, h: usize) -> bool;
+}
+
+impl EigenElem for f64 {
+    /// Francis double-shift step, which keeps the arithmetic real. Blocks must have at least 3
+    /// rows
+    unsafe fn qr_step(m: &mut DataMatrix<f64>, l: usize, h: usize, exceptional: bool) {
+        let n = m.rows();
+
+        // the shifts are the eigen values of the trailing 2x2, given by their sum and product
+        let (s, t) = if exceptional {
+            // break cycles with an ad hoc shift
+            let w = m.get_unchecked(h, h - 1).abs() + m.get_unchecked(h - 1, h - 2).abs();
+            (1.5 * w, w * w)
+        } else {
+            (
+                m.get_unchecked(h - 1, h - 1) + m.get_unchecked(h, h),
+                m.get_unchecked(h - 1, h - 1) * m.get_unchecked(h, h)
+                    - m.get_unchecked(h - 1, h) * m.get_unchecked(h, h - 1),
+            )
+        };
+
+        // first col of `(H - s1)(H - s2)`
+        let mut x = m.get_unchecked(l, l).powi(2) + m.get_unchecked(l, l + 1) * m.get_unchecked(l + 1, l)
+            - s * m.get_unchecked(l, l) + t;
+        let mut y = m.get_unchecked(l + 1, l) * (m.get_unchecked(l, l) + m.get_unchecked(l + 1, l + 1) - s);
+        let mut z = m.get_unchecked(l + 1, l) * m.get_unchecked(l + 2, l + 1);
+
+        // chase the bulge down to the bottom
+        for k in l..h - 1 {
+            if let Some(v) = householder_vec(&[x, y, z]) {
+                let c0 = if k > l { k - 1 } else { l };
+                reflect_rows(m, &v, k, c0..n);
+                reflect_cols(m, &v, k, 0..(k + 4).min(h + 1));
+                if k > l {
+                    *m.get_mut_unchecked(k + 1, k - 1) = 0.0;
+                    *m.get_mut_unchecked(k + 2, k - 1) = 0.0;
+                }
+            }
+            x = *m.get_unchecked(k + 1, k);
+            y = *m.get_unchecked(k + 2, k);
+            if k + 3 <= h {
+                z = *m.get_unchecked(k + 3, k);
+            }
+        }
+        if let Some(v) = householder_vec(&[x, y]) {
+            reflect_rows(m, &v, h - 1, h - 2..n);
+            reflect_cols(m, &v, h - 1, 0..h + 1);
+            *m.get_mut_unchecked(h, h - 2) = 0.0;
+        }
+    }
+
+    /// Rotate the block into upper triangle if its eigen values are real, otherwise leave the
+    /// complex pair in it
+    unsafe fn split_2x2(m: &mut DataMatrix<f64>, h: usize) -> bool {
+        let n = m.rows();
+        let g = h - 1;
+
+        let x = *m.get_unchecked(h, h);
+        let p = (m.get_unchecked(g, g) - x) / 2.0;
+        let w = m.get_unchecked(h, g) * m.get_unchecked(g, h);
+        let q = p * p + w;
+        if q < 0.0 {
+            return true;
+        }
+
+        let z = p + q.sqrt().copysign(p);
+        let c = *m.get_unchecked(h, g);
+        let s = c.abs() + z.abs();
+        if s == 0.0 {
+            return true;
+        }
+        let (mut p, mut q) = (c / s, z / s);
+        let r = p.hypot(q);
+        p /= r;
+        q /= r;
+
+        for j in g..n {
+            let a = *m.get_unchecked(g, j);
+            let b = *m.get_unchecked(h, j);
+            *m.get_mut_unchecked(g, j) = q * a + p * b;
+            *m.get_mut_unchecked(h, j) = q * b - p * a;
+        }
+        for i in 0..=h {
+            let a = *m.get_unchecked(i, g);
+            let b = *m.get_unchecked(i, h);
+            *m.get_mut_unchecked(i, g) = q * a + p * b;
+            *m.get_mut_unchecked(i, h) = q * b - p * a;
+        }
+        *m.get_mut_unchecked(h, g) = 0.0;
+        true
+    }
+}
+
+impl EigenElem for Complex {
+    /// Single-shift step with Wilkinson shift, done by Givens rotations. Blocks must have at
+    /// least 2 rows
+    unsafe fn qr_step(m: &mut DataMatrix<Complex>, l: usize, h: usize, exceptional: bool) {
+        complex_qr_step(m, l, h, exceptional, None);
+    }
+
+    unsafe fn split_2x2(_: &mut DataMatrix<Complex>, _: usize) -> bool {
+        false
     }
 }
 
+/// How an [`EigenValueSolver`] ended up
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConvergenceReport {
+    /// Number of QR steps taken
+    pub iterations: usize,
+    /// The largest sub-diagnol element that was dropped or is still unconverged, which bounds the
+    /// backward error of the eigen values
+    pub residual: f64,
+    /// If all eigen values (or complex pairs) have been isolated
+    pub converged: bool,
+}
 
 /// To solve eigen values
-pub struct EigenValueSolver {
-    pub mat: DataMatrix<f64>,
+///
+/// Each step is a shifted QR step on the Hessenberg matrix (see [`EigenElem`]). Once a
+/// sub-diagnol element is negligible, the matrix is split there and the converged bottom block
+/// is left alone, so that the matrix ends up (quasi) upper triangular. For real matrices, `2x2`
+/// blocks are left for complex pairs.
+pub struct EigenValueSolver<T: EigenElem = f64> {
+    pub mat: DataMatrix<T>,
+    /// Rows `active..` are already converged
+    active: usize,
+    /// Relative tolerance for deflation
+    tol: f64,
+    /// Used in place of the diagnol when it is zero
+    norm: f64,
+    iterations: usize,
+    /// Steps since the last deflation
+    stall: usize,
+    dropped: f64,
 }
 
-impl EigenValueSolver {
+impl<T: EigenElem> EigenValueSolver<T> {
     /// Create a new solver. `mat` is transformed into hessenberg in this method
-    pub fn new(mat: DataMatrix<f64>) -> Result<Self, MatError> {
+    pub fn new(mat: DataMatrix<T>) -> Result<Self, MatError> {
         if mat.dimensions() == (0, 0) { return Err(MatError::EmptyMatrix); }
         if !mat.is_square() {
             return Err(MatError::NotSquare { dim: mat.dimensions() });
@@ -154,79 +441,144 @@ impl EigenValueSolver {
 
         Ok(unsafe { Self::new_unchecked(mat, )} )
     }
-    pub unsafe fn new_unchecked(mut mat: DataMatrix<f64>) -> Self {
+    pub unsafe fn new_unchecked(mut mat: DataMatrix<T>) -> Self {
         hessenberg_unchecked(&mut mat);
-        Self { mat, }
+        let n = mat.rows();
+        let mut norm = 0.0f64;
+        for i in 0..n { for j in 0..n {
+            norm = norm.max(mat.get_unchecked(i, j).normal());
+        }}
+        Self {
+            mat,
+            active: n,
+            tol: f64::EPSILON,
+            norm,
+            iterations: 0,
+            stall: 0,
+            dropped: 0.0,
+        }
     }
     /// Read the diagnoal of the matrix. Caution that this is not the eigen values
     /// because there might be complex eigen values.
-    pub fn read_diag(&self) -> Vec<f64> {
+    pub fn read_diag(&self) -> Vec<T> {
         let mut v = Vec::with_capacity(self.mat.rows());
         for i in 0..self.mat.rows() {
-            unsafe { v.push(*self.mat.get_unchecked(i, i)); }
+            unsafe { v.push(self.mat.get_unchecked(i, i).clone()); }
         }
         v
     }
-    /// Iter until the delta of elements on the diagnol is smaller than `epsilon`
-    /// or, after a `max_iter`, then return the matrix
-    pub fn eigen_mat(mut self, epsilon: f64, max_iter: usize) -> DataMatrix<f64>{
-        let mut cnt = 0;
-        while let Some(delta) = self.next() {
-            if delta < epsilon { break; }
-            if cnt > max_iter { break; }
-            cnt += 1;
-        } 
-        self.mat
+    /// If all eigen values have been isolated
+    pub fn is_converged(&self) -> bool {
+        self.active == 0
+    }
+    /// Report iterations taken and the residual so far
+    pub fn report(&self) -> ConvergenceReport {
+        let mut residual = self.dropped;
+        for k in 1..self.active {
+            residual = residual.max(unsafe { self.mat.get_unchecked(k, k - 1).normal() });
+        }
+        ConvergenceReport {
+            iterations: self.iterations,
+            residual,
+            converged: self.is_converged(),
+        }
+    }
+    /// Iter until all eigen values converge, or after a `max_iter`, then return the matrix
+    ///
+    /// A sub-diagnol element is taken as zero once it is smaller than `epsilon` relative to its
+    /// neighbours on the diagnol; `epsilon` below [`f64::EPSILON`] makes no difference
+    pub fn eigen_mat(self, epsilon: f64, max_iter: usize) -> DataMatrix<T> {
+        self.eigen_mat_with_report(epsilon, max_iter).0
+    }
+    /// Same as `eigen_mat`, also reporting how it converged
+    pub fn eigen_mat_with_report(mut self, epsilon: f64, max_iter: usize) -> (DataMatrix<T>, ConvergenceReport) {
+        self.tol = epsilon.max(f64::EPSILON);
+        while self.iterations < max_iter && self.next().is_some() {}
+        let report = self.report();
+        (self.mat, report)
     }
     /// Call `eigen_mat` first, then calculate eigen values from the result of
     /// `eigen_mat`
     pub fn eigen_values(self, epsilon: f64, max_iter: usize) -> Vec<Complex> {
+        self.eigen_values_with_report(epsilon, max_iter).0
+    }
+    /// Same as `eigen_values`, also reporting how it converged
+    pub fn eigen_values_with_report(self, epsilon: f64, max_iter: usize) -> (Vec<Complex>, ConvergenceReport) {
 
-        let mat = self.eigen_mat(epsilon, max_iter);
+        let (mat, report) = self.eigen_mat_with_report(epsilon, max_iter);
 
         let n = mat.rows();
         let mut k = 0;
         let mut eigvals = Vec::new();
         unsafe {
             while k < n  {
-                if k < n - 1 && !mat.get_unchecked(k + 1, k).is_add_zero() {
-                    let a = mat.get_unchecked(k, k);
-                    let b = mat.get_unchecked(k, k + 1);
-                    let c = mat.get_unchecked(k + 1, k);
-                    let d = mat.get_unchecked(k + 1, k + 1);
-                    let (ev1, ev2) = eigval_2dim(*a, *b, *c, *d);
+                if k < n - 1 && mat.get_unchecked(k + 1, k).normal() != 0.0 {
+                    let a = mat.get_unchecked(k, k).clone().into();
+                    let b = mat.get_unchecked(k, k + 1).clone().into();
+                    let c = mat.get_unchecked(k + 1, k).clone().into();
+                    let d = mat.get_unchecked(k + 1, k + 1).clone().into();
+                    let (ev1, ev2) = eigval_2dim(a, b, c, d);
                     eigvals.push(ev1);
                     eigvals.push(ev2);
                     k += 2;
                 } else {
-                    eigvals.push(Complex::from(*mat.get_unchecked(k, k)));
+                    eigvals.push(mat.get_unchecked(k, k).clone().into());
                     k += 1;
                 }
             }
         }
 
-        eigvals
+        (eigvals, report)
     }
 }
 
-impl Iterator for EigenValueSolver {
+impl<T: EigenElem> Iterator for EigenValueSolver<T> {
+    /// The magnitude of the sub-diagnol elements at the bottom of the active block
     type Item = f64;
+    /// Deflate converged blocks, then do one QR step on the bottom unconverged block.
+    /// Returns [`None`] once all eigen values converge
     fn next(&mut self) -> Option<Self::Item> {
         unsafe {
-            let old_diag = DataMatrix::new_unchecked(self.read_diag(), 1, self.mat.rows());
-
-            let q = qr_unchecked(&mut self.mat);
-            let r = &self.mat;
-
-            self.mat = r.dot_unchecked(&q);
+            loop {
+                if self.active == 0 { return None; }
+                let h = self.active - 1;
+
+                // look for a negligible sub-diagnol element, splitting the matrix
+                let mut l = h;
+                while l > 0 {
+                    let sub = self.mat.get_unchecked(l, l - 1).normal();
+                    let mut scale = self.mat.get_unchecked(l, l).normal() + self.mat.get_unchecked(l - 1, l - 1).normal();
+                    if scale == 0.0 { scale = self.norm; }
+                    if sub <= self.tol * scale {
+                        self.dropped = self.dropped.max(sub);
+                        *self.mat.get_mut_unchecked(l, l - 1) = T::add_zero();
+                        break;
+                    }
+                    l -= 1;
+                }
 
-            let diag = DataMatrix::new_unchecked(self.read_diag(), 1, self.mat.rows());
-            let delta = col_normal_sqr_unchecked(&old_diag.sub(&diag), 0) ;
-            Some(delta)
+                if l == h {
+                    self.active -= 1;
+                    self.stall = 0;
+                } else if l + 1 == h && T::split_2x2(&mut self.mat, h) {
+                    self.active -= 2;
+                    self.stall = 0;
+                } else {
+                    self.stall += 1;
+                    self.iterations += 1;
+                    T::qr_step(&mut self.mat, l, h, self.stall.is_multiple_of(10));
+                    let mut residual = self.mat.get_unchecked(h, h - 1).normal();
+                    if h - l >= 2 {
+                        residual = residual.max(self.mat.get_unchecked(h - 1, h - 2).normal());
+                    }
+                    return Some(residual);
+                }
+            }
         }
     }
 }
 
+
 #[cfg(test)]
 mod test {
 
@@ -312,4 +664,107 @@ mod test {
             panic!("{:?}", &evs);
         }
     }
+
+    #[test]
+    fn test_eigenvals_companion() {
+        // companion matrix of (x-1)(x-2)(x-3)(x-4)(x^2+1)
+        let m: DataMatrix<f64> = mat_![
+            10 (-36) 60 (-59) 50 (-24);
+            1 0 0 0 0 0;
+            0 1 0 0 0 0;
+            0 0 1 0 0 0;
+            0 0 0 1 0 0;
+            0 0 0 0 1 0;
+        ].convert();
+
+        let (evs, report) = EigenValueSolver::new(m)
+            .unwrap()
+            .eigen_values_with_report(0.0, 999);
+
+        assert!(report.converged);
+        assert!(report.iterations < 50);
+        for ev in [Complex(1.0, 0.0), Complex(2.0, 0.0), Complex(3.0, 0.0), Complex(4.0, 0.0), Complex(0.0, 1.0), Complex(0.0, -1.0)] {
+            assert!(evs.iter().any(|x| (*x - &ev).normal() < 1e-8), "{:?} not in {:?}", ev, evs);
+        }
+    }
+
+    #[test]
+    fn test_eigenvals_cyclic() {
+        // all eigen values share the same modulus, on which unshifted QR makes no progress
+        let m = mat_![
+            0.0 0.0 1.0;
+            1.0 0.0 0.0;
+            0.0 1.0 0.0;
+        ];
+        let mut solver = EigenValueSolver::new(m).unwrap();
+        while solver.next().is_some() {}
+        let report = solver.report();
+        assert!(report.converged);
+        assert!(report.residual < 1e-12);
+
+        let (evs, _) = solver.eigen_values_with_report(0.0, 0);
+        let half_sqrt3 = 3f64.sqrt() / 2.0;
+        for ev in [Complex(1.0, 0.0), Complex(-0.5, half_sqrt3), Complex(-0.5, -half_sqrt3)] {
+            assert!(evs.iter().any(|x| (*x - &ev).normal() < 1e-8), "{:?} not in {:?}", ev, evs);
+        }
+    }
+
+    #[test]
+    fn test_qr_complex() {
+        let mut m = mat_![
+            (Complex(1.0, 1.0)) (Complex(2.0, 0.0)) (Complex(0.0, -1.0));
+            (Complex(0.0, 2.0)) (Complex(3.0, -1.0)) (Complex(1.0, 0.0));
+            (Complex(4.0, 0.0)) (Complex(1.0, 1.0)) (Complex(2.0, 2.0));
+        ];
+        let old_m = m.clone_data();
+
+        let q = qr(&mut m).unwrap();
+        for i in 0..3 { for j in 0..i {
+            assert!(m.get(i, j).unwrap().normal() < 1e-9);
+        }}
+
+        let back = old_m.sub(&q.dot(&m).unwrap());
+        let qhq = adjoint(&q).dot(&q).unwrap().sub(&DataMatrix::identity(3));
+        for i in 0..3 { for j in 0..3 {
+            assert!(back.get(i, j).unwrap().normal() < 1e-9);
+            assert!(qhq.get(i, j).unwrap().normal() < 1e-9);
+        }}
+    }
+
+    #[test]
+    fn test_eigenvals_complex() {
+        // hermitian, so the eigen values are real
+        let m = mat_![
+            (Complex(2.0, 0.0)) (Complex(1.0, -1.0));
+            (Complex(1.0, 1.0)) (Complex(3.0, 0.0));
+        ];
+        let (evs, report) = EigenValueSolver::new(m).unwrap().eigen_values_with_report(0.0, 999);
+        assert!(report.converged);
+        for ev in [Complex(1.0, 0.0), Complex(4.0, 0.0)] {
+            assert!(evs.iter().any(|x| (*x - &ev).normal() < 1e-8), "{:?} not in {:?}", ev, evs);
+        }
+
+        // upper triangle plus a similarity, eigen values `i`, `2 - i` and `-1`
+        let t = mat_![
+            (Complex(0.0, 1.0)) (Complex(1.0, 0.0)) (Complex(2.0, 1.0));
+            (Complex(0.0, 0.0)) (Complex(2.0, -1.0)) (Complex(0.0, 3.0));
+            (Complex(0.0, 0.0)) (Complex(0.0, 0.0)) (Complex(-1.0, 0.0));
+        ];
+        let s = mat_![
+            (Complex(1.0, 0.0)) (Complex(0.0, 0.0)) (Complex(0.0, 0.0));
+            (Complex(1.0, 1.0)) (Complex(1.0, 0.0)) (Complex(0.0, 0.0));
+            (Complex(0.0, -2.0)) (Complex(3.0, 0.0)) (Complex(1.0, 0.0));
+        ];
+        let s_inv = mat_![
+            (Complex(1.0, 0.0)) (Complex(0.0, 0.0)) (Complex(0.0, 0.0));
+            (Complex(-1.0, -1.0)) (Complex(1.0, 0.0)) (Complex(0.0, 0.0));
+            (Complex(3.0, 5.0)) (Complex(-3.0, 0.0)) (Complex(1.0, 0.0));
+        ];
+        let m = s.dot(&t).unwrap().dot(&s_inv).unwrap();
+        let (evs, report) = EigenValueSolver::new(m).unwrap().eigen_values_with_report(0.0, 999);
+        assert!(report.converged);
+        for ev in [Complex(0.0, 1.0), Complex(2.0, -1.0), Complex(-1.0, 0.0)] {
+            assert!(evs.iter().any(|x| (*x - &ev).normal() < 1e-8), "{:?} not in {:?}", ev, evs);
+        }
+    }
 }
@@ -0,0 +1,270 @@
+use crate::error::MatError;
+use crate::matrix::{DataMatrix, Mat};
+use crate::Complex;
+
+use super::{EigenElem, EigenValueSolver, LUDecomposition};
+
+/// At most this many solves are done for each eigen vector, it usually takes one or two
+const MAX_INVERSE_ITER: usize = 16;
+
+/// `x -= (v^H x) v` for each unit `v` in `basis`, done twice since once is not enough when `x`
+/// is nearly in the span of `basis`
+fn orthogonalize_against(x: &mut [Complex], basis: &[Vec<Complex>]) {
+    for v in basis.iter().chain(basis) {
+        let mut p = Complex(0.0, 0.0);
+        for (vi, xi) in v.iter().zip(x.iter()) {
+            p += &(vi.adjoint() * xi);
+        }
+        for (vi, xi) in v.iter().zip(x.iter_mut()) {
+            *xi -= &(p * vi);
+        }
+    }
+}
+
+/// Scale `x` to unit length with its largest element real and positive, or return `false` if
+/// it is zero
+fn normalize(x: &mut [Complex]) -> bool {
+    let norm = x.iter().map(|a| a.normal2()).sum::<f64>().sqrt();
+    let top = x.iter().copied().max_by(|a, b| a.normal2().total_cmp(&b.normal2()));
+    match top {
+        Some(top) if norm != 0.0 && norm.is_finite() => {
+            let scale = Complex(top.0, -top.1) * &Complex((top.normal() * norm).recip(), 0.0);
+            for a in x.iter_mut() {
+                *a *= &scale;
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+/// `|A x - lambda x|`
+unsafe fn residual(a: &DataMatrix<Complex>, lambda: Complex, x: &[Complex]) -> f64 {
+    let n = a.rows();
+    let mut sum = 0.0;
+    for i in 0..n {
+        let mut r = Complex(0.0, 0.0) - &(lambda * &x[i]);
+        for (j, xj) in x.iter().enumerate() {
+            r += &(*a.get_unchecked(i, j) * xj);
+        }
+        sum += r.normal2();
+    }
+    sum.sqrt()
+}
+
+/// Eigen vector of `a` for the approximate eigen value `lambda` by shifted inverse iteration,
+/// kept orthogonal to the vectors already found for eigen values close to `lambda`
+unsafe fn inverse_iteration(
+    a: &DataMatrix<Complex>,
+    lambda: Complex,
+    norm: f64,
+    tol: f64,
+    cluster: &[Vec<Complex>],
+) -> Result<Vec<Complex>, MatError> {
+    let n = a.rows();
+
+    // `lambda` is accurate up to rounding, so `A - lambda` might be exactly singular
+    let mut delta = norm * f64::EPSILON;
+    let lu = loop {
+        let mut shifted = a.clone_data();
+        let shift = lambda + &Complex(delta, 0.0);
+        for i in 0..n {
+            *shifted.get_mut_unchecked(i, i) -= &shift;
+        }
+        let lu = LUDecomposition::new_unchecked(&shifted)?;
+        if !lu.is_singular() {
+            break lu;
+        }
+        delta *= 1024.0;
+    };
+
+    // any unit vector not orthogonal to the eigen vector will do. Try a generic one first, then
+    // the unit vectors for one that is far enough from `cluster`
+    let generic: Vec<Complex> = (0..n).map(|i| Complex(1.0 + i as f64 / n as f64, 0.0)).collect();
+    let mut x = generic.clone();
+    for start in std::iter::once(generic).chain((0..n).map(|k| {
+        let mut e = vec![Complex(0.0, 0.0); n];
+        e[k] = Complex(1.0, 0.0);
+        e
+    })) {
+        let mut y = start;
+        normalize(&mut y);
+        orthogonalize_against(&mut y, cluster);
+        if y.iter().map(|a| a.normal2()).sum::<f64>() > 0.01 {
+            normalize(&mut y);
+            x = y;
+            break;
+        }
+    }
+
+    let mut best = (f64::INFINITY, x.clone());
+    for _ in 0..MAX_INVERSE_ITER {
+        let mut y = lu.solve(&DataMatrix::one_col(x.clone()))?.data();
+        orthogonalize_against(&mut y, cluster);
+        if !normalize(&mut y) {
+            break;
+        }
+        x = y;
+
+        let r = residual(a, lambda, &x);
+        if r < best.0 {
+            best = (r, x.clone());
+        }
+        if r <= tol {
+            break;
+        }
+    }
+
+    Ok(best.1)
+}
+
+/// Eigen values of a square `mat` from [`EigenValueSolver`], paired with eigen vectors in cols
+/// of the returned matrix, each normalized to unit length
+///
+/// The eigen vectors are found by shifted inverse iteration. Vectors of equal (or very close)
+/// eigen values are kept orthogonal, so for a defective `mat` not all of them are eigen vectors.
+/// Returns a [`MatError::NotConverged`] if the eigen values don't converge in `max_iter` steps
+#[allow(clippy::type_complexity)]
+pub fn eigenvectors<T: EigenElem>(
+    mat: &dyn Mat<Item = T>,
+    epsilon: f64,
+    max_iter: usize,
+) -> Result<(Vec<Complex>, DataMatrix<Complex>), MatError> {
+    let (eigvals, report) =
+        EigenValueSolver::new(mat.clone_data())?.eigen_values_with_report(epsilon, max_iter);
+    if !report.converged {
+        return Err(MatError::NotConverged {
+            iterations: report.iterations,
+        });
+    }
+
+    let n = mat.rows();
+    let mut a: DataMatrix<Complex> = DataMatrix::zeros(n, n);
+    let mut norm = 0.0f64;
+    unsafe {
+        for i in 0..n {
+            for j in 0..n {
+                let x: Complex = mat.get_unchecked(i, j).clone().into();
+                norm = norm.max(x.normal());
+                *a.get_mut_unchecked(i, j) = x;
+            }
+        }
+    }
+    if norm == 0.0 {
+        norm = 1.0;
+    }
+    let tol = epsilon.max(f64::EPSILON) * norm * n as f64;
+    // eigen values this close can't be told apart, so their vectors are kept orthogonal
+    let gap = f64::EPSILON.sqrt() * norm;
+
+    let mut vecs: Vec<Vec<Complex>> = Vec::with_capacity(n);
+    for (k, lambda) in eigvals.iter().enumerate() {
+        let cluster: Vec<Vec<Complex>> = (0..k)
+            .filter(|&j| (eigvals[j] - lambda).normal() <= gap)
+            .map(|j| vecs[j].clone())
+            .collect();
+        vecs.push(unsafe { inverse_iteration(&a, *lambda, norm, tol, &cluster)? });
+    }
+
+    let mut result = DataMatrix::zeros(n, n);
+    for (j, v) in vecs.into_iter().enumerate() {
+        for (i, x) in v.into_iter().enumerate() {
+            unsafe { *result.get_mut_unchecked(i, j) = x }
+        }
+    }
+    Ok((eigvals, result))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use mat_macro::mat_;
+
+    fn check_eigenvectors(a: &DataMatrix<Complex>, vals: &[Complex], vecs: &DataMatrix<Complex>) {
+        let n = a.rows();
+        for (j, lambda) in vals.iter().enumerate() {
+            let v: Vec<Complex> = (0..n).map(|i| *vecs.get(i, j).unwrap()).collect();
+            let len = v.iter().map(|x| x.normal2()).sum::<f64>();
+            assert!((len - 1.0).abs() < 1e-9);
+            assert!(unsafe { residual(a, *lambda, &v) } < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_eigenvectors() {
+        let m = mat_![
+            2.0 0.0 0.0;
+            0.0 3.0 4.0;
+            0.0 4.0 9.0;
+        ];
+        let (vals, vecs) = eigenvectors(&m, 0.0, 999).unwrap();
+        for ev in [1.0, 2.0, 11.0] {
+            assert!(vals.iter().any(|x| (*x - &Complex(ev, 0.0)).normal() < 1e-9));
+        }
+        let a = m.convert();
+        check_eigenvectors(&a, &vals, &vecs);
+    }
+
+    #[test]
+    fn test_eigenvectors_complex() {
+        // rotation, with eigen values `i` and `-i`
+        let m = mat_![
+            0.0 (-1.0);
+            1.0 0.0;
+        ];
+        let (vals, vecs) = eigenvectors(&m, 0.0, 999).unwrap();
+        assert!(vals.iter().all(|x| (x.normal() - 1.0).abs() < 1e-9 && x.re().abs() < 1e-9));
+        check_eigenvectors(&m.convert(), &vals, &vecs);
+
+        let m = mat_![
+            (Complex(2.0, 0.0)) (Complex(1.0, -1.0));
+            (Complex(1.0, 1.0)) (Complex(3.0, 0.0));
+        ];
+        let (vals, vecs) = eigenvectors(&m, 0.0, 999).unwrap();
+        check_eigenvectors(&m, &vals, &vecs);
+    }
+
+    #[test]
+    fn test_eigenvectors_scaled() {
+        // elements far below the tolerance of `is_add_zero`, which must not make `A - lambda`
+        // look singular
+        for scale in [1e-6, 1e-8] {
+            let mut m = mat_![
+                2.0 0.0 0.0;
+                0.0 3.0 4.0;
+                0.0 4.0 9.0;
+            ];
+            m.scale(&scale);
+            let (vals, vecs) = eigenvectors(&m, 0.0, 999).unwrap();
+            let a: DataMatrix<Complex> = m.convert();
+            for (j, lambda) in vals.iter().enumerate() {
+                let v: Vec<Complex> = (0..3).map(|i| *vecs.get(i, j).unwrap()).collect();
+                assert!(unsafe { residual(&a, *lambda, &v) } < 1e-12 * scale);
+            }
+        }
+    }
+
+    #[test]
+    fn test_eigenvectors_repeated() {
+        let m = mat_![
+            2.0 0.0 0.0;
+            0.0 2.0 0.0;
+            0.0 0.0 2.0;
+        ];
+        let (vals, vecs) = eigenvectors(&m, 0.0, 999).unwrap();
+        let a = m.convert();
+        check_eigenvectors(&a, &vals, &vecs);
+
+        // the vectors span the whole eigen space
+        for i in 0..3 {
+            for j in 0..3 {
+                let mut p = Complex(0.0, 0.0);
+                for k in 0..3 {
+                    p += &(vecs.get(k, i).unwrap().adjoint() * vecs.get(k, j).unwrap());
+                }
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((p - &Complex(expected, 0.0)).normal() < 1e-9);
+            }
+        }
+    }
+}
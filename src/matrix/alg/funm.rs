@@ -0,0 +1,360 @@
+use crate::error::MatError;
+use crate::matrix::{DataMatrix, Mat};
+use crate::Complex;
+use MatError::*;
+
+use super::{schur, EigenElem, LUDecomposition};
+
+/// Coefficients of the `[m/m]` Padé approximants of `e^x` for `m = 3, 5, 7, 9`, with the largest
+/// 1-norm each is accurate to double precision on, from Higham's "The scaling and squaring
+/// method for the matrix exponential revisited"
+const PADE: [(&[f64], f64); 4] = [
+    (&[120.0, 60.0, 12.0, 1.0], 1.495585217958292e-2),
+    (&[30240.0, 15120.0, 3360.0, 420.0, 30.0, 1.0], 2.53939833006323e-1),
+    (
+        &[17297280.0, 8648640.0, 1995840.0, 277200.0, 25200.0, 1512.0, 56.0, 1.0],
+        9.504178996162932e-1,
+    ),
+    (
+        &[
+            17643225600.0, 8821612800.0, 2075673600.0, 302702400.0, 30270240.0, 2162160.0, 110880.0,
+            3960.0, 90.0, 1.0,
+        ],
+        2.097847961257068,
+    ),
+];
+
+/// Same as [`PADE`] for `m = 13`, which is used with scaling for larger norms
+const PADE_13: [f64; 14] = [
+    64764752532480000.0,
+    32382376266240000.0,
+    7771770303897600.0,
+    1187353796428800.0,
+    129060195264000.0,
+    10559470521600.0,
+    670442572800.0,
+    33522128640.0,
+    1323241920.0,
+    40840800.0,
+    960960.0,
+    16380.0,
+    182.0,
+    1.0,
+];
+const THETA_13: f64 = 5.371920351148152;
+
+/// Gauss-Legendre nodes and weights on `[0, 1]`, as the `[8/8]` Padé approximant of
+/// `log(1 + x)` in partial fractions
+const GAUSS_LEGENDRE: [(f64, f64); 8] = [
+    (0.019855071751231856, 0.05061426814518813),
+    (0.10166676129318664, 0.11119051722668724),
+    (0.2372337950418355, 0.15685332293894363),
+    (0.4082826787521751, 0.181341891689181),
+    (0.591717321247825, 0.181341891689181),
+    (0.7627662049581645, 0.15685332293894363),
+    (0.8983332387068134, 0.11119051722668724),
+    (0.9801449282487681, 0.05061426814518813),
+];
+
+/// Largest 1-norm of `T - I` at which [`GAUSS_LEGENDRE`] is accurate to double precision
+const LOG_THETA: f64 = 0.25;
+
+/// 1-norm, the largest absolute col sum
+fn norm1<T: EigenElem>(m: &dyn Mat<Item = T>) -> f64 {
+    (0..m.cols())
+        .map(|j| (0..m.rows()).map(|i| unsafe { m.get_unchecked(i, j) }.normal()).sum::<f64>())
+        .fold(0.0, f64::max)
+}
+
+/// `id * I + sum(c * m)` for `(c, m)` in `terms`
+fn combine<T: EigenElem>(n: usize, id: f64, terms: &[(f64, &DataMatrix<T>)]) -> DataMatrix<T> {
+    let mut r: DataMatrix<T> = DataMatrix::identity(n);
+    r.scale(&T::from_f64(id));
+    for (c, m) in terms {
+        let c = T::from_f64(*c);
+        for i in 0..n {
+            for j in 0..n {
+                unsafe { *r.get_mut_unchecked(i, j) += &(c.clone() * m.get_unchecked(i, j)) }
+            }
+        }
+    }
+    r
+}
+
+fn check_square<T: EigenElem>(mat: &dyn Mat<Item = T>) -> Result<(), MatError> {
+    if mat.dimensions() == (0, 0) {
+        return Err(EmptyMatrix);
+    }
+    if !mat.is_square() {
+        return Err(NotSquare {
+            dim: mat.dimensions(),
+        });
+    }
+    Ok(())
+}
+
+/// The matrix exponential `e^mat` of a square `mat`
+///
+/// This is the scaling and squaring method: `mat` is scaled by `2^-s` so that a Padé
+/// approximant of degree up to `13` is accurate, and the result is squared `s` times. The
+/// arithmetic is done in `T`, so a real `mat` gives a real result. Solution of `x' = Ax` is
+/// `x(t) = e^(At) x(0)`
+pub fn expm<T: EigenElem>(mat: &dyn Mat<Item = T>) -> Result<DataMatrix<T>, MatError> {
+    check_square(mat)?;
+
+    let n = mat.rows();
+    let mut a = mat.clone_data();
+    let norm = norm1(&a);
+    let a2 = unsafe { a.dot_unchecked(&a) };
+
+    // `u` holds odd terms and `v` even terms, so that the approximant is `(v - u)^-1 (v + u)`
+    let (u, v, s) = if let Some((b, _)) = PADE.iter().find(|(_, theta)| norm <= *theta) {
+        let mut powers = vec![DataMatrix::identity(n), a2];
+        while powers.len() * 2 < b.len() {
+            let next = unsafe { powers.last().unwrap().dot_unchecked(&powers[1]) };
+            powers.push(next);
+        }
+        let odd: Vec<(f64, &DataMatrix<T>)> = powers.iter().enumerate().skip(1).map(|(k, p)| (b[2 * k + 1], p)).collect();
+        let even: Vec<(f64, &DataMatrix<T>)> = powers.iter().enumerate().skip(1).map(|(k, p)| (b[2 * k], p)).collect();
+        let u = unsafe { a.dot_unchecked(&combine(n, b[1], &odd)) };
+        (u, combine(n, b[0], &even), 0)
+    } else {
+        let s = (norm / THETA_13).log2().ceil().max(0.0) as i32;
+        let scale = 2f64.powi(-s);
+        a.scale(&T::from_f64(scale));
+        let b = &PADE_13;
+        let a2 = combine(n, 0.0, &[(scale * scale, &a2)]);
+        let a4 = unsafe { a2.dot_unchecked(&a2) };
+        let a6 = unsafe { a4.dot_unchecked(&a2) };
+
+        let high = combine(n, 0.0, &[(b[13], &a6), (b[11], &a4), (b[9], &a2)]);
+        let low = combine(n, b[1], &[(b[7], &a6), (b[5], &a4), (b[3], &a2)]);
+        let u = unsafe { a.dot_unchecked(&combine(n, 0.0, &[(1.0, &a6.dot_unchecked(&high)), (1.0, &low)])) };
+
+        let high = combine(n, 0.0, &[(b[12], &a6), (b[10], &a4), (b[8], &a2)]);
+        let low = combine(n, b[0], &[(b[6], &a6), (b[4], &a4), (b[2], &a2)]);
+        let v = unsafe { combine(n, 0.0, &[(1.0, &a6.dot_unchecked(&high)), (1.0, &low)]) };
+        (u, v, s)
+    };
+
+    let p = combine(n, 0.0, &[(1.0, &v), (1.0, &u)]);
+    let q = combine(n, 0.0, &[(1.0, &v), (-1.0, &u)]);
+    let mut r = LUDecomposition::new(&q)?.solve(&p)?;
+    for _ in 0..s {
+        r = unsafe { r.dot_unchecked(&r) };
+    }
+    Ok(r)
+}
+
+/// Principal square root of the upper triangular `t`, by the recurrence of Björck and
+/// Hammarling
+fn sqrtm_triangular(t: &DataMatrix<Complex>) -> Result<DataMatrix<Complex>, MatError> {
+    let n = t.rows();
+    let mut r = DataMatrix::zeros(n, n);
+    unsafe {
+        for j in 0..n {
+            *r.get_mut_unchecked(j, j) = t.get_unchecked(j, j).sqrt();
+            for i in (0..j).rev() {
+                let mut x = *t.get_unchecked(i, j);
+                for k in i + 1..j {
+                    x -= &(*r.get_unchecked(i, k) * r.get_unchecked(k, j));
+                }
+                let d = *r.get_unchecked(i, i) + r.get_unchecked(j, j);
+                *r.get_mut_unchecked(i, j) = if d.normal() != 0.0 {
+                    x / &d
+                } else if x.normal() == 0.0 {
+                    x
+                } else {
+                    // a zero eigen value that isn't semisimple
+                    return Err(NotInDomain { function: "sqrtm" });
+                };
+            }
+        }
+    }
+    Ok(r)
+}
+
+/// `q f q^H`, converted back into `T`, failing if the imaginary part can't be dropped
+fn from_schur<T>(q: &DataMatrix<Complex>, f: &DataMatrix<Complex>) -> Result<DataMatrix<T>, MatError>
+where
+    T: EigenElem + From<Complex>,
+{
+    let n = q.rows();
+    let qf = unsafe { q.dot_unchecked(f) };
+    let mut r = DataMatrix::zeros(n, n);
+    let mut lost = 0.0;
+    unsafe {
+        for i in 0..n {
+            for j in 0..n {
+                let mut x = Complex(0.0, 0.0);
+                for k in 0..n {
+                    x += &(*qf.get_unchecked(i, k) * &q.get_unchecked(j, k).adjoint());
+                }
+                let y = T::from(x);
+                lost += (Into::<Complex>::into(y.clone()) - &x).normal();
+                *r.get_mut_unchecked(i, j) = y;
+            }
+        }
+    }
+    if lost > f64::EPSILON.sqrt() * norm1(f).max(1.0) {
+        return Err(ComplexResult);
+    }
+    Ok(r)
+}
+
+/// Principal square root of a square `mat`, the one whose eigen values have non-negative real
+/// parts
+///
+/// With the Schur decomposition `mat = Q T Q^H`, this is `Q sqrt(T) Q^H`, where the root of the
+/// triangle `T` is solved element by element. A real `mat` with negative eigen values has no
+/// real principal root, and then [`MatError::ComplexResult`] is returned. If there are repeated
+/// zero eigen values, the root may not exist, which is [`MatError::NotInDomain`]
+pub fn sqrtm<T>(mat: &dyn Mat<Item = T>) -> Result<DataMatrix<T>, MatError>
+where
+    T: EigenElem + From<Complex>,
+{
+    check_square(mat)?;
+    let (q, t) = schur(mat)?;
+    from_schur(&q, &sqrtm_triangular(&t)?)
+}
+
+/// Principal logarithm of a square `mat`, the one whose eigen values have imaginary parts in
+/// `(-pi, pi]`, so that `expm(logm(mat)) = mat`
+///
+/// This is the inverse scaling and squaring method on the Schur form `T`: square roots are
+/// taken until `T` is close to `I`, where `log(T)` is approximated by a Padé approximant, then
+/// the result is scaled back. Singular matrices have no logarithm, for which
+/// [`MatError::NotInDomain`] is returned; as with [`sqrtm`], real matrices with negative
+/// eigen values give [`MatError::ComplexResult`]
+pub fn logm<T>(mat: &dyn Mat<Item = T>) -> Result<DataMatrix<T>, MatError>
+where
+    T: EigenElem + From<Complex>,
+{
+    check_square(mat)?;
+    let (q, mut t) = schur(mat)?;
+    let n = t.rows();
+    // eigen values of the Schur form are only accurate up to rounding relative to `T`
+    let tol = n as f64 * f64::EPSILON * norm1(&t);
+    if (0..n).any(|i| unsafe { t.get_unchecked(i, i) }.normal() <= tol) {
+        return Err(NotInDomain { function: "logm" });
+    }
+
+    let id: DataMatrix<Complex> = DataMatrix::identity(n);
+    let mut k = 0;
+    while norm1(&t.sub(&id)) > LOG_THETA {
+        // each root halves the arguments of the eigen values, so this terminates
+        t = sqrtm_triangular(&t)?;
+        k += 1;
+    }
+
+    let x = t.sub(&id);
+    let mut log = DataMatrix::zeros(n, n);
+    for (node, weight) in GAUSS_LEGENDRE {
+        let mut d = x.clone_data();
+        d.scale(&Complex(node, 0.0));
+        d.add_assign(&DataMatrix::<Complex>::identity(n));
+        let mut term = LUDecomposition::new(&d)?.solve(&x)?;
+        term.scale(&Complex(weight, 0.0));
+        log.add_assign(&term);
+    }
+    log.scale(&Complex(2f64.powi(k), 0.0));
+
+    from_schur(&q, &log)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use mat_macro::mat_;
+
+    fn close<T: EigenElem>(a: &DataMatrix<T>, b: &DataMatrix<T>, tol: f64) -> bool {
+        let mut d = a.clone_data();
+        d.sub_assign(b);
+        norm1(&d) <= tol * norm1(b).max(1.0)
+    }
+
+    #[test]
+    fn test_expm() {
+        // rotation
+        let a: DataMatrix<f64> = mat_![
+            0.0 (-1.0);
+            1.0 0.0;
+        ];
+        let (c, s) = (1f64.cos(), 1f64.sin());
+        assert!(close(&expm(&a).unwrap(), &mat_![c (-s); s c;], 1e-14));
+
+        // nilpotent, `I + N + N^2 / 2`
+        let a: DataMatrix<f64> = mat_![
+            0.0 1.0 0.0;
+            0.0 0.0 1.0;
+            0.0 0.0 0.0;
+        ];
+        assert!(close(&expm(&a).unwrap(), &mat_![1.0 1.0 0.5; 0.0 1.0 1.0; 0.0 0.0 1.0;], 1e-14));
+
+        // large norm, needing scaling
+        let a: DataMatrix<f64> = mat_![
+            (-49.0) 24.0;
+            (-64.0) 31.0;
+        ];
+        let (e1, e17) = ((-1f64).exp(), (-17f64).exp());
+        let expected: DataMatrix<f64> = mat_![
+            (3.0 * e17 - 2.0 * e1) (1.5 * e1 - 1.5 * e17);
+            (4.0 * e17 - 4.0 * e1) (3.0 * e1 - 2.0 * e17);
+        ];
+        assert!(close(&expm(&a).unwrap(), &expected, 1e-12));
+
+        let a = mat_![(Complex(0.0, std::f64::consts::PI));];
+        assert!(close(&expm(&a).unwrap(), &mat_![(Complex(-1.0, 0.0));], 1e-14));
+    }
+
+    #[test]
+    fn test_sqrtm() {
+        let a: DataMatrix<f64> = mat_![
+            4.0 1.0 0.0;
+            1.0 5.0 2.0;
+            0.0 2.0 6.0;
+        ];
+        let r = sqrtm(&a).unwrap();
+        assert!(close(&r.dot(&r).unwrap(), &a, 1e-12));
+
+        // `-I` has no real root, but `iI` is a complex one
+        let a: DataMatrix<f64> = mat_![
+            (-1.0) 0.0;
+            0.0 (-1.0);
+        ];
+        assert!(matches!(sqrtm(&a), Err(ComplexResult)));
+        let a: DataMatrix<Complex> = a.convert();
+        let r = sqrtm(&a).unwrap();
+        assert!(close(&r.dot(&r).unwrap(), &a, 1e-12));
+
+        let a: DataMatrix<f64> = mat_![
+            0.0 1.0;
+            0.0 0.0;
+        ];
+        assert!(matches!(sqrtm(&a), Err(NotInDomain { .. })));
+    }
+
+    #[test]
+    fn test_logm() {
+        let a: DataMatrix<f64> = mat_![
+            1.0 2.0 0.0;
+            0.0 3.0 1.0;
+            1.0 0.0 4.0;
+        ];
+        let l = logm(&a).unwrap();
+        assert!(close(&expm(&l).unwrap(), &a, 1e-12));
+
+        // `log(e^(At)) = At` for small `t`
+        let a: DataMatrix<Complex> = mat_![
+            (Complex(0.1, 0.2)) (Complex(0.0, -0.3));
+            (Complex(0.5, 0.0)) (Complex(-0.2, 0.1));
+        ];
+        assert!(close(&logm(&expm(&a).unwrap()).unwrap(), &a, 1e-12));
+
+        let a: DataMatrix<f64> = mat_![
+            1.0 1.0;
+            1.0 1.0;
+        ];
+        assert!(matches!(logm(&a), Err(NotInDomain { .. })));
+    }
+}
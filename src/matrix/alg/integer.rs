@@ -0,0 +1,420 @@
+use super::super::eliminated_matrix::{checked_add, checked_mul, checked_sub};
+use super::SolveResult;
+use crate::element::IntegerElem;
+use crate::error::MatError;
+use crate::matrix::{checked_dot, DataMatrix, Mat};
+use MatError::*;
+
+/// Euclidean quotient of `a` by `b`, so that `0 <= a - q*b < |b|`, or
+/// [`MatError::Overflow`] if it overflows or `b` is zero
+fn div_euclid<T: IntegerElem>(a: &T, b: &T) -> Result<T, MatError> {
+    a.checked_div_euclid(b).ok_or(Overflow)
+}
+
+fn neg<T: IntegerElem>(x: &T) -> Result<T, MatError> {
+    checked_sub(&T::add_zero(), x)
+}
+
+fn abs<T: IntegerElem>(x: &T) -> Result<T, MatError> {
+    if *x < T::add_zero() {
+        neg(x)
+    } else {
+        Ok(x.clone())
+    }
+}
+
+/// Quotient of `a` by `b` rounded to the nearest integer, so that `|a - q*b| <= |b|/2`
+fn div_round<T: IntegerElem>(a: &T, b: &T) -> Result<T, MatError> {
+    let q = div_euclid(a, b)?;
+    let r = checked_sub(a, &checked_mul(&q, b)?)?;
+    // `r` is in `0..|b|`, so `r - |b|` is the other candidate
+    let b_abs = abs(b)?;
+    if checked_sub(&r, &b_abs)? < neg(&r)? {
+        Ok(q)
+    } else if *b < T::add_zero() {
+        checked_sub(&q, &T::mul_zero())
+    } else {
+        checked_add(&q, &T::mul_zero())
+    }
+}
+
+/// `(g, x, y)` with `a*x + b*y = g`, where `g` is the positive gcd of `a > 0` and `b != 0`,
+/// and `|x| <= |b/g|/2` so that the coefficients stay small
+fn ext_gcd<T: IntegerElem>(a: &T, b: &T) -> Result<(T, T, T), MatError> {
+    let (mut r0, mut r1) = (a.clone(), b.clone());
+    let (mut s0, mut s1) = (T::mul_zero(), T::add_zero());
+    while !r1.is_add_zero() {
+        let q = div_euclid(&r0, &r1)?;
+        let r = checked_sub(&r0, &checked_mul(&q, &r1)?)?;
+        let s = checked_sub(&s0, &checked_mul(&q, &s1)?)?;
+        (r0, r1) = (r1, r);
+        (s0, s1) = (s1, s);
+    }
+    let g = abs(&r0)?;
+    let x = if r0 < T::add_zero() { neg(&s0)? } else { s0 };
+
+    let b_g = div_euclid(b, &g)?;
+    let x = checked_sub(&x, &checked_mul(&div_round(&x, &b_g)?, &b_g)?)?;
+    // exact, as `g - a*x` is a multiple of `b`
+    let y = div_euclid(&checked_sub(&g, &checked_mul(a, &x)?)?, b)?;
+    Ok((g, x, y))
+}
+
+/// Replace rows `i`, `j` of `m` with `(a*row_i + b*row_j, c*row_i + d*row_j)`
+unsafe fn combine_rows<T: IntegerElem>(m: &mut DataMatrix<T>, i: usize, j: usize, coef: [&T; 4]) -> Result<(), MatError> {
+    let [a, b, c, d] = coef;
+    for k in 0..m.cols() {
+        let (x, y) = (m.get_unchecked(i, k).clone(), m.get_unchecked(j, k).clone());
+        *m.get_mut_unchecked(i, k) = checked_add(&checked_mul(a, &x)?, &checked_mul(b, &y)?)?;
+        *m.get_mut_unchecked(j, k) = checked_add(&checked_mul(c, &x)?, &checked_mul(d, &y)?)?;
+    }
+    Ok(())
+}
+
+/// Subtract `q` times row `src` from row `dst` of `m`
+unsafe fn sub_row<T: IntegerElem>(m: &mut DataMatrix<T>, dst: usize, src: usize, q: &T) -> Result<(), MatError> {
+    let (one, zero, nq) = (T::mul_zero(), T::add_zero(), neg(q)?);
+    combine_rows(m, dst, src, [&one, &nq, &zero, &one])
+}
+
+/// Negate row `i` of `m`
+unsafe fn negate_row<T: IntegerElem>(m: &mut DataMatrix<T>, i: usize) -> Result<(), MatError> {
+    for k in 0..m.cols() {
+        *m.get_mut_unchecked(i, k) = neg(m.get_unchecked(i, k))?;
+    }
+    Ok(())
+}
+
+/// Hermite normal form of an integer `mat`, returning `(H, U)` where `U * mat = H` and `U` is
+/// unimodular
+///
+/// `H` is in row echelon form, its pivots are positive and the elements above each pivot are
+/// in `0..pivot`. Unlike [`EliminatedMatrix`](crate::EliminatedMatrix), only unimodular row
+/// operations are used, so `H` spans the same lattice as `mat` over the integers.
+///
+/// This is the Hermite normal form of `[mat | I]`, built by adding one row at a time and
+/// reducing after each, so the rows of `U` below the rank of `mat`, which span its left
+/// kernel, are reduced too and intermediate results stay small.
+///
+/// Fails with [`MatError::Overflow`] if intermediate results overflow
+#[allow(clippy::type_complexity)]
+pub fn hermite_normal_form<T>(mat: &dyn Mat<Item = T>) -> Result<(DataMatrix<T>, DataMatrix<T>), MatError>
+where
+    T: IntegerElem,
+{
+    let (m, n) = mat.dimensions();
+    let mut a = DataMatrix::zeros(m, n + m);
+    unsafe {
+        for i in 0..m {
+            for j in 0..n {
+                *a.get_mut_unchecked(i, j) = mat.get_unchecked(i, j).clone();
+            }
+            *a.get_mut_unchecked(i, n + i) = T::mul_zero();
+        }
+    }
+
+    // `[mat | I]` has full rank, so after adding row `k`, rows `0..=k` all have pivots
+    let mut pivots: Vec<usize> = Vec::with_capacity(m);
+    unsafe {
+        for k in 0..m {
+            loop {
+                let c = (0..n + m).find(|&j| !a.get_unchecked(k, j).is_add_zero()).unwrap();
+                let Some(p) = pivots.iter().position(|&pc| pc == c) else {
+                    // a new pivot, moved into place by the order of the cols
+                    let p = pivots.partition_point(|&pc| pc < c);
+                    for i in (p..k).rev() {
+                        a.row_unchecked(i).swap_unchecked(&mut a.row_unchecked(i + 1));
+                    }
+                    pivots.insert(p, c);
+                    if *a.get_unchecked(p, c) < T::add_zero() {
+                        negate_row(&mut a, p)?;
+                    }
+                    break;
+                };
+
+                // reduce by the pivot row, then replace both rows with their gcd and a row
+                // that is zero at `c`
+                let pivot = a.get_unchecked(p, c).clone();
+                let q = div_round(a.get_unchecked(k, c), &pivot)?;
+                sub_row(&mut a, k, p, &q)?;
+                let x = a.get_unchecked(k, c).clone();
+                if x.is_add_zero() {
+                    continue;
+                }
+                let (g, s, t) = ext_gcd(&pivot, &x)?;
+                let (pivot_g, x_g) = (div_euclid(&pivot, &g)?, neg(&div_euclid(&x, &g)?)?);
+                combine_rows(&mut a, p, k, [&s, &t, &x_g, &pivot_g])?;
+            }
+
+            // reduce the elements above each pivot
+            for i in 0..=k {
+                for (j, &c) in pivots.iter().enumerate().skip(i + 1) {
+                    let q = div_euclid(a.get_unchecked(i, c), a.get_unchecked(j, c))?;
+                    if !q.is_add_zero() {
+                        sub_row(&mut a, i, j, &q)?;
+                    }
+                }
+            }
+        }
+    }
+
+    let mut h = DataMatrix::zeros(m, n);
+    let mut u = DataMatrix::zeros(m, m);
+    unsafe {
+        for i in 0..m {
+            for j in 0..n {
+                *h.get_mut_unchecked(i, j) = a.get_unchecked(i, j).clone();
+            }
+            for j in 0..m {
+                *u.get_mut_unchecked(i, j) = a.get_unchecked(i, n + j).clone();
+            }
+        }
+    }
+    Ok((h, u))
+}
+
+/// Smith normal form of an integer `mat`, returning `(U, S, V)` where `U * mat * V = S` and
+/// `U`, `V` are unimodular
+///
+/// `S` is diagnol, with non-negative elements `d_1, d_2, ...` where each `d_i` divides
+/// `d_(i+1)`; these are the invariant factors of `mat`.
+///
+/// Computed by taking the [`hermite_normal_form`] of the rows and of the cols in turn, until
+/// `S` is diagnol, which keeps `U` and `V` reduced.
+///
+/// Fails with [`MatError::Overflow`] if intermediate results overflow
+#[allow(clippy::type_complexity)]
+pub fn smith_normal_form<T>(
+    mat: &dyn Mat<Item = T>,
+) -> Result<(DataMatrix<T>, DataMatrix<T>, DataMatrix<T>), MatError>
+where
+    T: IntegerElem,
+{
+    let (m, n) = mat.dimensions();
+    let mut s = mat.clone_data();
+    let mut u = DataMatrix::identity(m);
+    let mut v = DataMatrix::identity(n);
+    let is_diag = |s: &DataMatrix<T>| {
+        (0..m).all(|i| (0..n).all(|j| i == j || unsafe { s.get_unchecked(i, j) }.is_add_zero()))
+    };
+
+    unsafe {
+        loop {
+            let (h, hu) = hermite_normal_form(&s)?;
+            u = checked_dot(&hu, &u)?;
+            s = h;
+            if is_diag(&s) {
+                // each `d_i` must divide the rest, otherwise add the row of one it doesn't
+                // divide, and the pass over the cols replaces `d_i` by their gcd
+                let r = m.min(n);
+                let mut bad = None;
+                'find: for i in 0..r {
+                    let d = s.get_unchecked(i, i).clone();
+                    if d.is_add_zero() {
+                        break;
+                    }
+                    for j in i + 1..r {
+                        let x = s.get_unchecked(j, j);
+                        if !checked_mul(&div_euclid(x, &d)?, &d)?.eq(x) {
+                            bad = Some((i, j));
+                            break 'find;
+                        }
+                    }
+                }
+                let Some((i, j)) = bad else {
+                    break;
+                };
+                let one = neg(&T::mul_zero())?;
+                sub_row(&mut s, i, j, &one)?;
+                sub_row(&mut u, i, j, &one)?;
+            }
+
+            let (h, hv) = hermite_normal_form(&s.transposed())?;
+            v = checked_dot(&v, &hv.transposed())?;
+            s = h.transposed();
+        }
+    }
+
+    Ok((u, s, v))
+}
+
+/// Solve `coef * x = b` over the integers, by the Smith normal form of `coef`
+///
+/// With `U * coef * V = S`, the equation becomes `S * y = U * b` where `x = V * y`, and all
+/// integer solutions are read from the diagnol of `S`. For [`SolveResult::Infinite`], any
+/// integer combination of the cols of `general` can be added to `special`
+pub fn solve_integer<T>(coef: &dyn Mat<Item = T>, b: &dyn Mat<Item = T>) -> Result<SolveResult<T>, MatError>
+where
+    T: IntegerElem,
+{
+    if b.cols() != 1 || b.rows() != coef.rows() {
+        return Err(InconsistentDimension {
+            need: (coef.rows(), 1),
+            got: b.dimensions(),
+        });
+    }
+
+    let (m, n) = coef.dimensions();
+    let (u, s, v) = smith_normal_form(coef)?;
+    let c = unsafe { checked_dot(&u, b)? };
+
+    let mut y = DataMatrix::zeros(n, 1);
+    let mut rank = 0;
+    unsafe {
+        for i in 0..m {
+            let d = if i < n { s.get_unchecked(i, i).clone() } else { T::add_zero() };
+            let ci = c.get_unchecked(i, 0);
+            if d.is_add_zero() {
+                if !ci.is_add_zero() {
+                    return Ok(SolveResult::None);
+                }
+                continue;
+            }
+            let q = div_euclid(ci, &d)?;
+            if !checked_mul(&q, &d)?.eq(ci) {
+                return Ok(SolveResult::None);
+            }
+            *y.get_mut_unchecked(i, 0) = q;
+            rank += 1;
+        }
+    }
+
+    let special = unsafe { checked_dot(&v, &y)? };
+    if rank == n {
+        return Ok(SolveResult::Single(special));
+    }
+    let mut general = DataMatrix::zeros(n, n - rank);
+    for j in rank..n {
+        for i in 0..n {
+            unsafe { *general.get_mut_unchecked(i, j - rank) = v.get_unchecked(i, j).clone() }
+        }
+    }
+    Ok(SolveResult::Infinite { general, special })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::alg::det;
+    use mat_macro::mat_;
+
+    fn is_unimodular(u: &DataMatrix<i64>) -> bool {
+        det(u).unwrap().abs() == 1
+    }
+
+    #[test]
+    fn test_hermite_normal_form() {
+        let a: DataMatrix<i64> = mat_![
+            2 3 6 2;
+            5 6 1 6;
+            8 3 1 1;
+        ];
+        let (h, u) = hermite_normal_form(&a).unwrap();
+        assert!(is_unimodular(&u));
+        assert_eq!(u.dot(&a).unwrap(), h);
+
+        // echelon, with positive pivots and reduced elements above
+        let mut last = None;
+        for i in 0..h.rows() {
+            let Some(p) = (0..h.cols()).find(|&j| *h.get(i, j).unwrap() != 0) else {
+                continue;
+            };
+            assert!(last.is_none_or(|l| p > l));
+            last = Some(p);
+            let pivot = *h.get(i, p).unwrap();
+            assert!(pivot > 0);
+            for k in 0..i {
+                assert!((0..pivot).contains(h.get(k, p).unwrap()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_smith_normal_form() {
+        let a: DataMatrix<i64> = mat_![
+            2 4 4;
+            (-6) 6 12;
+            10 (-4) (-16);
+        ];
+        let (u, s, v) = smith_normal_form(&a).unwrap();
+        assert!(is_unimodular(&u) && is_unimodular(&v));
+        assert_eq!(u.dot(&a).unwrap().dot(&v).unwrap(), s);
+        assert_eq!(s, DataMatrix::with_diag(vec![2, 6, 12]));
+
+        let a: DataMatrix<i64> = mat_![
+            6 4;
+            4 0;
+            0 2;
+        ];
+        let (u, s, v) = smith_normal_form(&a).unwrap();
+        assert_eq!(u.dot(&a).unwrap().dot(&v).unwrap(), s);
+        // gcd of the entries is `2`, and of the `(2, 2)` minors is `4`
+        assert_eq!((*s.get(0, 0).unwrap(), *s.get(1, 1).unwrap()), (2, 2));
+    }
+
+    #[test]
+    fn test_solve_integer() {
+        let a: DataMatrix<i64> = mat_![2 4;];
+        match solve_integer(&a, &mat_![6;]).unwrap() {
+            SolveResult::Infinite { general, special } => {
+                assert_eq!(a.dot(&special).unwrap(), mat_![6;]);
+                assert_eq!(a.dot(&general).unwrap(), mat_![0;]);
+            }
+            _ => panic!("Expected infinite solutions"),
+        }
+        // solvable over rationals, but not integers
+        assert!(matches!(solve_integer(&a, &mat_![3;]).unwrap(), SolveResult::None));
+
+        let a: DataMatrix<i64> = mat_![
+            2 1;
+            1 1;
+        ];
+        match solve_integer(&a, &mat_![3; 2;]).unwrap() {
+            SolveResult::Single(x) => assert_eq!(x, mat_![1; 1;]),
+            _ => panic!("Expected a single solution"),
+        }
+    }
+
+    #[test]
+    fn test_integer_forms_random() {
+        // the unreduced forms overflow `i64` on most of these
+        let mut seed = 0x9E3779B97F4A7C15u64;
+        for _ in 0..20 {
+            let data = (0..80)
+                .map(|_| {
+                    seed ^= seed << 13;
+                    seed ^= seed >> 7;
+                    seed ^= seed << 17;
+                    (seed % 19) as i64 - 9
+                })
+                .collect();
+            let a = DataMatrix::new(data, 10, 8).unwrap();
+            let (h, u) = hermite_normal_form(&a).unwrap();
+            assert_eq!(u.dot(&a).unwrap(), h);
+            let (u, s, v) = smith_normal_form(&a).unwrap();
+            assert_eq!(u.dot(&a).unwrap().dot(&v).unwrap(), s);
+
+            let x = DataMatrix::one_col((1..=8).collect());
+            match solve_integer(&a, &a.dot(&x).unwrap()).unwrap() {
+                SolveResult::Single(y) => assert_eq!(y, x),
+                _ => panic!("Expected a single solution"),
+            }
+        }
+    }
+
+    #[cfg(feature = "big_rational")]
+    #[test]
+    fn test_smith_normal_form_bigint() {
+        use num_bigint::BigInt;
+
+        // overflows `i64` during elimination
+        let a: DataMatrix<BigInt> = mat_![
+            (BigInt::from(i64::MAX)) (BigInt::from(2));
+            (BigInt::from(3)) (BigInt::from(i64::MAX));
+        ];
+        let (u, s, v) = smith_normal_form(&a).unwrap();
+        assert_eq!(u.dot(&a).unwrap().dot(&v).unwrap(), s);
+        let d = BigInt::from(i64::MAX) * BigInt::from(i64::MAX) - BigInt::from(6);
+        assert_eq!(s, DataMatrix::with_diag(vec![BigInt::from(1), d]));
+    }
+}
@@ -0,0 +1,190 @@
+use crate::error::MatError;
+use crate::matrix::{DataMatrix, Mat};
+use MatError::*;
+
+/// Cyclic sweeps allowed before giving up, convergence is quadratic so a few usually do
+const MAX_SWEEPS: usize = 64;
+
+/// Check that `mat` is square and symmetric, up to rounding relative to its largest element
+fn check_symmetric(mat: &dyn Mat<Item = f64>) -> Result<(), MatError> {
+    if mat.dimensions() == (0, 0) {
+        return Err(EmptyMatrix);
+    }
+    if !mat.is_square() {
+        return Err(NotSquare {
+            dim: mat.dimensions(),
+        });
+    }
+
+    let n = mat.rows();
+    let mut norm = 0.0f64;
+    unsafe {
+        for i in 0..n {
+            for j in 0..n {
+                norm = norm.max(mat.get_unchecked(i, j).abs());
+            }
+        }
+        for i in 0..n {
+            for j in 0..i {
+                let diff = mat.get_unchecked(i, j) - mat.get_unchecked(j, i);
+                if diff.is_nan() || diff.abs() > 8.0 * f64::EPSILON * norm {
+                    return Err(NotSymmetric);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Rotate rows and cols `p`, `q` of the symmetric `a` to zero `a[p, q]`, accumulating the
+/// rotation into cols of `v`
+unsafe fn rotate(a: &mut DataMatrix<f64>, v: &mut DataMatrix<f64>, p: usize, q: usize) {
+    let apq = *a.get_unchecked(p, q);
+    let theta = (a.get_unchecked(q, q) - a.get_unchecked(p, p)) / (2.0 * apq);
+    // the smaller angle, `tan` of which is the smaller root of `t^2 + 2*theta*t - 1`
+    let t = if theta.abs() > 1e150 {
+        0.5 / theta
+    } else {
+        theta.signum() / (theta.abs() + theta.hypot(1.0))
+    };
+    let c = t.hypot(1.0).recip();
+    let s = t * c;
+
+    let n = a.rows();
+    for k in 0..n {
+        let (x, y) = (*a.get_unchecked(k, p), *a.get_unchecked(k, q));
+        *a.get_mut_unchecked(k, p) = c * x - s * y;
+        *a.get_mut_unchecked(k, q) = s * x + c * y;
+    }
+    for k in 0..n {
+        let (x, y) = (*a.get_unchecked(p, k), *a.get_unchecked(q, k));
+        *a.get_mut_unchecked(p, k) = c * x - s * y;
+        *a.get_mut_unchecked(q, k) = s * x + c * y;
+    }
+    *a.get_mut_unchecked(p, q) = 0.0;
+    *a.get_mut_unchecked(q, p) = 0.0;
+
+    for k in 0..n {
+        let (x, y) = (*v.get_unchecked(k, p), *v.get_unchecked(k, q));
+        *v.get_mut_unchecked(k, p) = c * x - s * y;
+        *v.get_mut_unchecked(k, q) = s * x + c * y;
+    }
+}
+
+/// Eigen values and eigen vectors of a real symmetric `mat` by cyclic Jacobi rotations,
+/// returning `(values, V)` where `mat = V * diag(values) * V^T`
+///
+/// The eigen values are sorted in ascending order, and col `i` of the orthogonal `V` is the
+/// eigen vector of `values[i]`. Unlike [`EigenValueSolver`](super::EigenValueSolver), the
+/// eigen vectors are orthogonal to working precision even for repeated eigen values.
+///
+/// Returns a [`MatError::NotSymmetric`] if `mat` isn't symmetric
+pub fn jacobi_eigen(mat: &dyn Mat<Item = f64>) -> Result<(Vec<f64>, DataMatrix<f64>), MatError> {
+    check_symmetric(mat)?;
+
+    let n = mat.rows();
+    let mut a = mat.clone_data();
+    let mut v = DataMatrix::identity(n);
+
+    let frobenius = |a: &DataMatrix<f64>, off: bool| {
+        let mut sum = 0.0;
+        for i in 0..n {
+            for j in 0..n {
+                if !off || i != j {
+                    sum += unsafe { a.get_unchecked(i, j).powi(2) };
+                }
+            }
+        }
+        sum.sqrt()
+    };
+    let tol = f64::EPSILON * frobenius(&a, false);
+
+    let mut sweeps = 0;
+    while frobenius(&a, true) > tol {
+        if sweeps == MAX_SWEEPS {
+            return Err(NotConverged { iterations: sweeps });
+        }
+        sweeps += 1;
+        for p in 0..n {
+            for q in p + 1..n {
+                if unsafe { *a.get_unchecked(p, q) } != 0.0 {
+                    unsafe { rotate(&mut a, &mut v, p, q) };
+                }
+            }
+        }
+    }
+
+    let mut values: Vec<f64> = (0..n).map(|i| unsafe { *a.get_unchecked(i, i) }).collect();
+    for i in 0..n {
+        let k = (i..n).min_by(|&x, &y| values[x].total_cmp(&values[y])).unwrap();
+        if k != i {
+            values.swap(i, k);
+            unsafe { v.col_unchecked(i).swap_unchecked(&mut v.col_unchecked(k)) };
+        }
+    }
+
+    Ok((values, v))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use mat_macro::mat_;
+
+    fn check_jacobi(a: &DataMatrix<f64>) -> Vec<f64> {
+        let (values, v) = jacobi_eigen(a).unwrap();
+        let n = a.rows();
+        let vt = v.clone_data().transposed();
+
+        let vtv = vt.dot(&v).unwrap();
+        let av = a.dot(&v).unwrap();
+        for i in 0..n {
+            for (j, value) in values.iter().enumerate() {
+                let id = if i == j { 1.0 } else { 0.0 };
+                assert!((vtv.get(i, j).unwrap() - id).abs() < 1e-12);
+                assert!((av.get(i, j).unwrap() - v.get(i, j).unwrap() * value).abs() < 1e-12);
+            }
+        }
+        for w in values.windows(2) {
+            assert!(w[0] <= w[1]);
+        }
+        values
+    }
+
+    #[test]
+    fn test_jacobi() {
+        let a = mat_![
+            2.0 (-1.0) 0.0;
+            (-1.0) 2.0 (-1.0);
+            0.0 (-1.0) 2.0;
+        ];
+        let values = check_jacobi(&a);
+        let r = 2f64.sqrt();
+        for (x, y) in values.iter().zip([2.0 - r, 2.0, 2.0 + r]) {
+            assert!((x - y).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_jacobi_repeated() {
+        // eigen values 0, 0, 0, 4
+        let a = mat_![
+            1.0 1.0 1.0 1.0;
+            1.0 1.0 1.0 1.0;
+            1.0 1.0 1.0 1.0;
+            1.0 1.0 1.0 1.0;
+        ];
+        let values = check_jacobi(&a);
+        assert!((values[3] - 4.0).abs() < 1e-12);
+        assert!(values[..3].iter().all(|x| x.abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_not_symmetric() {
+        let a = mat_![
+            1.0 2.0;
+            3.0 1.0;
+        ];
+        assert!(matches!(jacobi_eigen(&a), Err(MatError::NotSymmetric)));
+    }
+}
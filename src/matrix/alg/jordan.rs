@@ -0,0 +1,241 @@
+use super::charpoly;
+use crate::element::{AddZero, MulZero};
+use crate::error::MatError;
+use crate::matrix::{checked_dot, DataMatrix, EliminatedMatrix, Mat};
+use crate::Rational;
+use MatError::*;
+
+/// Rank of the matrix with `cols` as its cols, each of length `n`
+fn rank_of(cols: &[Vec<Rational>], n: usize) -> Result<usize, MatError> {
+    if cols.is_empty() {
+        return Ok(0);
+    }
+    let mut m = DataMatrix::zeros(n, cols.len());
+    for (j, col) in cols.iter().enumerate() {
+        for (i, x) in col.iter().enumerate() {
+            unsafe { *m.get_mut_unchecked(i, j) = x.clone() }
+        }
+    }
+    Ok(EliminatedMatrix::eliminated(m)?.rank())
+}
+
+/// Cols of a [`DataMatrix`] as vectors
+fn cols_of(m: &DataMatrix<Rational>) -> Vec<Vec<Rational>> {
+    (0..m.cols())
+        .map(|j| (0..m.rows()).map(|i| unsafe { m.get_unchecked(i, j).clone() }).collect())
+        .collect()
+}
+
+/// `m * v`, reporting overflow
+fn apply(m: &DataMatrix<Rational>, v: &[Rational]) -> Result<Vec<Rational>, MatError> {
+    let mut result = Vec::with_capacity(v.len());
+    for i in 0..m.rows() {
+        let mut x = Rational::add_zero();
+        for (j, vj) in v.iter().enumerate() {
+            let y = unsafe { m.get_unchecked(i, j) }.checked_mul(vj).ok_or(Overflow)?;
+            x = x.checked_add(&y).ok_or(Overflow)?;
+        }
+        result.push(x);
+    }
+    Ok(result)
+}
+
+/// Jordan chains of eigen value `lambda` with algebraic multiplicity `m`, each from the eigen
+/// vector up, longest first
+fn chains(mat: &dyn Mat<Item = Rational>, lambda: &Rational, m: usize) -> Result<Vec<Vec<Vec<Rational>>>, MatError> {
+    let n = mat.rows();
+    let mut nmat = mat.clone_data();
+    for i in 0..n {
+        unsafe {
+            let x = nmat.get_unchecked(i, i).checked_sub(lambda).ok_or(Overflow)?;
+            *nmat.get_mut_unchecked(i, i) = x;
+        }
+    }
+
+    // bases of the null spaces of `N^k` for `k = 0, 1, ...`, until the dimension reaches `m`
+    let mut kernels: Vec<Vec<Vec<Rational>>> = vec![Vec::new()];
+    let mut power = DataMatrix::identity(n);
+    while kernels.last().unwrap().len() < m {
+        power = unsafe { checked_dot(&power, &nmat)? };
+        let kernel = match EliminatedMatrix::eliminated(power.clone_data())?.null_space()? {
+            Some(basis) => cols_of(&basis),
+            None => Vec::new(),
+        };
+        kernels.push(kernel);
+    }
+
+    // `(top, length)` of each chain, picking tops from the highest level down, where
+    // images of the tops picked so far are excluded
+    let mut tops: Vec<(Vec<Rational>, usize)> = Vec::new();
+    for k in (1..kernels.len()).rev() {
+        let mut span = kernels[k - 1].clone();
+        for (top, len) in tops.iter() {
+            let mut v = top.clone();
+            for _ in 0..len - k {
+                v = apply(&nmat, &v)?;
+            }
+            span.push(v);
+        }
+        let mut rank = rank_of(&span, n)?;
+        for v in kernels[k].iter() {
+            span.push(v.clone());
+            let r = rank_of(&span, n)?;
+            if r > rank {
+                rank = r;
+                tops.push((v.clone(), k));
+            } else {
+                span.pop();
+            }
+        }
+    }
+
+    let mut result = Vec::with_capacity(tops.len());
+    for (top, len) in tops {
+        let mut chain = vec![top];
+        for _ in 1..len {
+            let v = apply(&nmat, chain.last().unwrap())?;
+            chain.push(v);
+        }
+        chain.reverse();
+        result.push(chain);
+    }
+    Ok(result)
+}
+
+/// Jordan normal form of a square `mat` whose eigen values are all rational, returning
+/// `(P, J)` where `mat = P * J * P^-1`
+///
+/// Jordan blocks are ordered by eigen value ascending, and by size descending for the same
+/// eigen value. Each chain of generalized eigen vectors is built exactly from the null spaces
+/// of `(mat - λI)^k`, and is put into cols of `P` from the eigen vector up.
+///
+/// If any eigen value is not rational, returns a [`MatError::IrrationalSpectrum`], and if
+/// finding them overflows, a [`MatError::Overflow`]. Rational eigen values are found exactly by
+/// [`rational_roots`](crate::Polynomial::rational_roots), however close they are
+#[allow(clippy::type_complexity)]
+pub fn jordan_form(
+    mat: &dyn Mat<Item = Rational>,
+) -> Result<(DataMatrix<Rational>, DataMatrix<Rational>), MatError> {
+    if mat.dimensions() == (0, 0) {
+        return Err(EmptyMatrix);
+    }
+    if !mat.is_square() {
+        return Err(NotSquare {
+            dim: mat.dimensions(),
+        });
+    }
+
+    let (mut eigvals, rest) = charpoly(mat)?.rational_roots()?;
+    if rest.degree() > 0 {
+        return Err(IrrationalSpectrum);
+    }
+    eigvals.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+    let n = mat.rows();
+    let mut p = DataMatrix::zeros(n, n);
+    let mut j = DataMatrix::zeros(n, n);
+    let mut col = 0;
+    for (lambda, m) in eigvals {
+        for chain in chains(mat, &lambda, m)? {
+            for (k, v) in chain.into_iter().enumerate() {
+                unsafe {
+                    for (i, x) in v.into_iter().enumerate() {
+                        *p.get_mut_unchecked(i, col) = x;
+                    }
+                    *j.get_mut_unchecked(col, col) = lambda.clone();
+                    if k > 0 {
+                        *j.get_mut_unchecked(col - 1, col) = Rational::mul_zero();
+                    }
+                }
+                col += 1;
+            }
+        }
+    }
+
+    Ok((p, j))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::alg::det;
+    use crate::rational;
+    use mat_macro::mat_;
+
+    fn check_jordan(a: &DataMatrix<Rational>) -> DataMatrix<Rational> {
+        let (p, j) = jordan_form(a).unwrap();
+        assert!(!det(&p).unwrap().is_add_zero());
+        assert_eq!(a.dot(&p).unwrap(), p.dot(&j).unwrap());
+        j
+    }
+
+    #[test]
+    fn test_jordan_form() {
+        let a: DataMatrix<Rational> = mat_![
+            5 4 2 1;
+            0 1 (-1) (-1);
+            (-1) (-1) 3 0;
+            1 1 (-1) 2;
+        ]
+        .convert();
+        let j = check_jordan(&a);
+        let expected: DataMatrix<Rational> = mat_![
+            1 0 0 0;
+            0 2 0 0;
+            0 0 4 1;
+            0 0 0 4;
+        ]
+        .convert();
+        assert_eq!(j, expected);
+    }
+
+    #[test]
+    fn test_jordan_form_blocks() {
+        // two blocks of the same eigen value, sizes 2 and 1
+        let a: DataMatrix<Rational> = mat_![
+            3 1 0;
+            0 3 0;
+            0 0 3;
+        ]
+        .convert();
+        let b: DataMatrix<Rational> = mat_![
+            1 2 0;
+            0 1 1;
+            1 0 1;
+        ]
+        .convert();
+        let a = b.dot(&a).unwrap().dot(&crate::alg::inv(&mut b.clone_data()).unwrap()).unwrap();
+        let j = check_jordan(&a);
+        assert_eq!(*j.get(0, 1).unwrap(), rational!(1, 1));
+        assert_eq!(*j.get(1, 2).unwrap(), rational!(0, 1));
+    }
+
+    #[test]
+    fn test_irrational_spectrum() {
+        let a: DataMatrix<Rational> = mat_![
+            0 2;
+            1 0;
+        ]
+        .convert();
+        assert!(matches!(jordan_form(&a), Err(MatError::IrrationalSpectrum)));
+    }
+
+    #[test]
+    #[cfg(feature = "big_rational")]
+    fn test_jordan_close_eigen_values() {
+        // eigen values `1` and `1 + 10^-12`, which `f64` can't tell apart
+        let big = num_bigint::BigInt::from(10).pow(12);
+        let r = Rational::new(big.clone() + 1, big);
+        let a = DataMatrix::new(vec![rational!(1, 1), rational!(1, 1), rational!(0, 1), r.clone()], 2, 2).unwrap();
+        let j = check_jordan(&a);
+        assert_eq!(j, DataMatrix::with_diag(vec![rational!(1, 1), r]));
+    }
+
+    #[test]
+    #[cfg(not(any(feature = "i64_rational", feature = "big_rational")))]
+    fn test_jordan_overflow() {
+        // checking the eigen value `1/46341` overflows `i32`, which must not look irrational
+        let a = DataMatrix::new(vec![rational!(1, 46341), rational!(0, 1), rational!(0, 1), rational!(1, 1)], 2, 2).unwrap();
+        assert!(matches!(jordan_form(&a), Err(MatError::Overflow)));
+    }
+}
@@ -0,0 +1,135 @@
+use crate::element::LinearElem;
+use crate::error::MatError;
+use crate::{DataMatrix, Mat};
+use MatError::*;
+
+/// Kronecker product `a ⊗ b`, the block matrix whose block `(i, j)` is `a[i, j] * b`
+pub fn kron<T: LinearElem>(a: &dyn Mat<Item = T>, b: &dyn Mat<Item = T>) -> DataMatrix<T> {
+    let (br, bc) = b.dimensions();
+    let mut result = DataMatrix::zeros(a.rows() * br, a.cols() * bc);
+    for i in 0..a.rows() {
+        for j in 0..a.cols() {
+            let x = unsafe { a.get_unchecked(i, j) };
+            for k in 0..br {
+                for l in 0..bc {
+                    unsafe {
+                        *result.get_mut_unchecked(i * br + k, j * bc + l) = x.clone() * b.get_unchecked(k, l);
+                    }
+                }
+            }
+        }
+    }
+    result
+}
+
+/// Direct sum of `mats`, that is the block diagnol matrix with `mats` on its diagnol
+pub fn direct_sum<T: LinearElem>(mats: &[&dyn Mat<Item = T>]) -> DataMatrix<T> {
+    let rows = mats.iter().map(|m| m.rows()).sum();
+    let cols = mats.iter().map(|m| m.cols()).sum();
+    let mut result = DataMatrix::zeros(rows, cols);
+
+    let (mut i0, mut j0) = (0, 0);
+    for m in mats {
+        for i in 0..m.rows() {
+            for j in 0..m.cols() {
+                unsafe {
+                    *result.get_mut_unchecked(i0 + i, j0 + j) = m.get_unchecked(i, j).clone();
+                }
+            }
+        }
+        i0 += m.rows();
+        j0 += m.cols();
+    }
+    result
+}
+
+/// Stack the cols of `mat` into one col, so that `vec(A X B) = kron(B^T, A) vec(X)`
+pub fn vec<T: LinearElem>(mat: &dyn Mat<Item = T>) -> DataMatrix<T> {
+    let mut data = Vec::with_capacity(mat.rows() * mat.cols());
+    for j in 0..mat.cols() {
+        for i in 0..mat.rows() {
+            data.push(unsafe { mat.get_unchecked(i, j) }.clone());
+        }
+    }
+    DataMatrix::one_col(data)
+}
+
+/// Inverse of [`vec`], cutting the col `v` into `cols` cols of length `rows`
+///
+/// Fails with [`MatError::ExpectedVector`] if `v` has more than one col, or
+/// [`MatError::BadInitVector`] if it doesn't have `rows * cols` elements
+pub fn unvec<T: LinearElem>(v: &dyn Mat<Item = T>, rows: usize, cols: usize) -> Result<DataMatrix<T>, MatError> {
+    if v.cols() != 1 {
+        return Err(ExpectedVector);
+    }
+    if v.rows() != rows * cols {
+        return Err(BadInitVector { len: v.rows(), cols, rows });
+    }
+
+    let mut result = DataMatrix::zeros(rows, cols);
+    for j in 0..cols {
+        for i in 0..rows {
+            unsafe {
+                *result.get_mut_unchecked(i, j) = v.get_unchecked(j * rows + i, 0).clone();
+            }
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use mat_macro::mat_;
+
+    #[test]
+    fn test_kron() {
+        let a: DataMatrix<i32> = mat_![1 2; 0 3;];
+        let b: DataMatrix<i32> = mat_![0 1; 1 0;];
+        assert_eq!(
+            kron(&a, &b),
+            mat_![
+                0 1 0 2;
+                1 0 2 0;
+                0 0 0 3;
+                0 0 3 0;
+            ]
+        );
+        let c: DataMatrix<i32> = mat_![1 (-1);];
+        assert_eq!(kron(&c, &a.clone_data().transposed()), mat_![1 0 (-1) 0; 2 3 (-2) (-3);]);
+        assert_eq!(kron(&DataMatrix::zeros(0, 2), &a).dimensions(), (0, 4));
+    }
+
+    #[test]
+    fn test_direct_sum() {
+        let a: DataMatrix<i32> = mat_![1 2;];
+        let b: DataMatrix<i32> = mat_![3; 4;];
+        let e: DataMatrix<i32> = DataMatrix::zeros(0, 1);
+        assert_eq!(
+            direct_sum(&[&a, &e, &b]),
+            mat_![
+                1 2 0 0;
+                0 0 0 3;
+                0 0 0 4;
+            ]
+        );
+        assert_eq!(direct_sum::<i32>(&[]).dimensions(), (0, 0));
+    }
+
+    #[test]
+    fn test_vec() {
+        let a: DataMatrix<i32> = mat_![1 2; 3 4;];
+        let x: DataMatrix<i32> = mat_![1 0 2; (-1) 1 1;];
+        let b: DataMatrix<i32> = mat_![1 1; 0 2; 3 0;];
+        assert_eq!(vec(&x), mat_![1; (-1); 0; 1; 2; 1;]);
+        assert_eq!(unvec(&vec(&x), 2, 3).unwrap(), x);
+
+        // `vec(A X B) = kron(B^T, A) vec(X)`
+        let axb = a.dot(&x).unwrap().dot(&b).unwrap();
+        let k = kron(&b.clone_data().transposed(), &a);
+        assert_eq!(vec(&axb), k.dot(&vec(&x)).unwrap());
+
+        assert!(matches!(unvec(&x, 3, 2), Err(ExpectedVector)));
+        assert!(matches!(unvec(&vec(&x), 4, 2), Err(BadInitVector { len: 6, .. })));
+    }
+}
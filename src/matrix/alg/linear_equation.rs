@@ -46,26 +46,29 @@ mod display {
     }
 }
 
-pub fn solve_augmented<T: LinearElem>(augmented: impl Mat<Item = T>) -> SolveResult<T>
+/// Solve the linear equation whose augmented matrix is `augmented`
+///
+/// Returns [`MatError::Overflow`] if the elements overflow during elimination
+pub fn solve_augmented<T: LinearElem>(augmented: impl Mat<Item = T>) -> Result<SolveResult<T>, MatError>
 where
     T: LinearElem + Inv,
 {
-    let augmented = augmented.eliminated();
+    let augmented = augmented.eliminated()?;
 
     let coef_slice = augmented.slice(0, augmented.rows(), 0, augmented.cols() - 1);
     let b_slice = augmented.slice(0, augmented.rows(), augmented.cols() - 1, 1);
 
-    let special = coef_slice.special_solution(&b_slice);
+    let special = coef_slice.special_solution(&b_slice)?;
 
-    match special {
+    Ok(match special {
         None => SolveResult::None,
-        Some(special) => match coef_slice.null_space() {
+        Some(special) => match coef_slice.null_space()? {
             None => SolveResult::Single(special),
             Some(general) => {
                 SolveResult::Infinite { general, special }
             },
         },
-    }
+    })
 }
 
 /// Solve linear equation; supports occassion of infinite solution.
@@ -86,12 +89,12 @@ where
 
     let augmented = concated_mat_![coef b;]?;
 
-    Ok(solve_augmented(augmented))
+    solve_augmented(augmented)
 }
 
 #[cfg(test)]
 mod test {
-    use crate::Rational;
+    use crate::{rational, Rational};
 
     use super::*;
     use mat_macro::mat_;
@@ -172,8 +175,8 @@ mod test {
             None => panic!("No solution"),
             Single(_) => panic!("Got single solution"),
             Infinite { general, special } => {
-                assert_eq!(special, mat_![ (Rational(13, 27)); (Rational(-1, 9)); (Rational(0, 1)); ]);
-                assert_eq!(general, mat_![ (Rational(7, 27)); (Rational(-13, 9)); (Rational(1, 1)); ]);
+                assert_eq!(special, mat_![ (rational!(13, 27)); (rational!(-1, 9)); (rational!(0, 1)); ]);
+                assert_eq!(general, mat_![ (rational!(7, 27)); (rational!(-13, 9)); (rational!(1, 1)); ]);
             }
         }
     }
@@ -188,8 +191,8 @@ mod test {
             None => panic!("No solution"),
             Single(_) => panic!("Got single solution"),
             Infinite { general, special } => {
-                assert_eq!(special, mat_![ (Rational(13, 27)); (Rational(-1, 9)); (Rational(0, 1)); ]);
-                assert_eq!(general, mat_![ (Rational(7, 27)); (Rational(-13, 9)); (Rational(1, 1)); ]);
+                assert_eq!(special, mat_![ (rational!(13, 27)); (rational!(-1, 9)); (rational!(0, 1)); ]);
+                assert_eq!(general, mat_![ (rational!(7, 27)); (rational!(-13, 9)); (rational!(1, 1)); ]);
             }
         }
     }
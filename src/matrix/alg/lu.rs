@@ -0,0 +1,357 @@
+use super::super::eliminated_matrix::{checked_mul, checked_sub};
+use crate::element::{Inv, LinearElem, Normal};
+use crate::error::MatError;
+use crate::matrix::{DataMatrix, Mat};
+use MatError::*;
+
+/// LU decomposition with partial pivoting, `P*A = L*U`
+///
+/// Row `i` of `P*A` is row `perm[i]` of `A`, `L` is unit-lower triangular and `U` is upper
+/// triangular. Once factorized, systems with the same coefficients can be solved in O(n^2) per
+/// right-hand side, and the determinant comes for free.
+pub struct LUDecomposition<T>
+where
+    T: LinearElem,
+{
+    /// `L` below the diagnol (its diagnol is all `1`s) and `U` on and above it
+    lu: DataMatrix<T>,
+    perm: Vec<usize>,
+    /// If `perm` is an odd permutation
+    negative: bool,
+}
+
+impl<T> LUDecomposition<T>
+where
+    T: LinearElem + Inv + Normal,
+{
+    /// Factorize `mat`, which must be square
+    ///
+    /// Pivots are chosen by the largest [`Normal`]. Singular matrices can be factorized too, but
+    /// then [`solve`](LUDecomposition::solve) fails
+    pub fn new(mat: &dyn Mat<Item = T>) -> Result<Self, MatError> {
+        if !mat.is_square() {
+            return Err(NotSquare {
+                dim: mat.dimensions(),
+            });
+        }
+        unsafe { Self::new_unchecked(mat) }
+    }
+
+    /// Same as [`new`](LUDecomposition::new), without checking dimension
+    ///
+    /// # Safety
+    /// `mat` must be a square
+    pub unsafe fn new_unchecked(mat: &dyn Mat<Item = T>) -> Result<Self, MatError> {
+        let n = mat.rows();
+        let mut lu = mat.clone_data();
+        let mut perm: Vec<usize> = (0..n).collect();
+        let mut negative = false;
+
+        for k in 0..n {
+            // `normal` may underflow for tiny non-zero elements, so it only ranks the candidates,
+            // while zeros are tested exactly, as `is_add_zero` has a tolerance for floats
+            let p = match (k..n)
+                .filter(|&i| *lu.get_unchecked(i, k) != T::add_zero())
+                .max_by(|&i, &j| {
+                    lu.get_unchecked(i, k)
+                        .normal()
+                        .total_cmp(&lu.get_unchecked(j, k).normal())
+                }) {
+                Some(p) => p,
+                // the rest of the column is already zero
+                None => continue,
+            };
+            if p != k {
+                lu.row_unchecked(k).swap_unchecked(&mut lu.row_unchecked(p));
+                perm.swap(k, p);
+                negative = !negative;
+            }
+
+            let pivot_inv = lu.get_unchecked(k, k).clone().inv();
+            for i in k + 1..n {
+                let factor = checked_mul(lu.get_unchecked(i, k), &pivot_inv)?;
+                for j in k + 1..n {
+                    let val = checked_sub(
+                        lu.get_unchecked(i, j),
+                        &checked_mul(lu.get_unchecked(k, j), &factor)?,
+                    )?;
+                    *lu.get_mut_unchecked(i, j) = val;
+                }
+                *lu.get_mut_unchecked(i, k) = factor;
+            }
+        }
+
+        Ok(LUDecomposition { lu, perm, negative })
+    }
+
+    /// Dimension of the factorized square
+    pub fn size(&self) -> usize {
+        self.lu.rows()
+    }
+
+    /// Row `i` of `P*A` is row `perm()[i]` of `A`
+    pub fn perm(&self) -> &[usize] {
+        &self.perm
+    }
+
+    /// The permutation matrix `P`
+    pub fn p(&self) -> DataMatrix<T> {
+        let mut p = DataMatrix::zeros(self.size(), self.size());
+        for (i, &j) in self.perm.iter().enumerate() {
+            unsafe { *p.get_mut_unchecked(i, j) = T::mul_zero() }
+        }
+        p
+    }
+
+    /// The unit-lower triangular `L`
+    pub fn l(&self) -> DataMatrix<T> {
+        let mut l = DataMatrix::identity(self.size());
+        for i in 0..self.size() {
+            for j in 0..i {
+                unsafe { *l.get_mut_unchecked(i, j) = self.lu.get_unchecked(i, j).clone() }
+            }
+        }
+        l
+    }
+
+    /// The upper triangular `U`
+    pub fn u(&self) -> DataMatrix<T> {
+        let mut u = DataMatrix::zeros(self.size(), self.size());
+        for i in 0..self.size() {
+            for j in i..self.size() {
+                unsafe { *u.get_mut_unchecked(i, j) = self.lu.get_unchecked(i, j).clone() }
+            }
+        }
+        u
+    }
+
+    /// If the factorized matrix is singular, that is some pivot of `U` is exactly zero
+    pub fn is_singular(&self) -> bool {
+        (0..self.size()).any(|i| unsafe { *self.lu.get_unchecked(i, i) == T::add_zero() })
+    }
+
+    /// Rank of the factorized matrix, where elements of `U` are zero relative to its largest one
+    fn rank(&self) -> Result<usize, MatError> {
+        let mut u = self.u();
+        let top = match u.iter().max_by(|a, b| a.normal().total_cmp(&b.normal())) {
+            Some(top) if *top != T::add_zero() => top.clone().inv(),
+            _ => return Ok(0),
+        };
+        // `EliminatedMatrix` tells zeros by `is_add_zero`, so scale `U` to unit size first
+        for x in u.iter_mut() {
+            *x = checked_mul(x, &top)?;
+        }
+        Ok(u.eliminated()?.rank())
+    }
+
+    /// Determinant of the factorized matrix, the product of pivots
+    pub fn det(&self) -> Result<T, MatError> {
+        let mut result = if self.negative {
+            T::add_zero() - &T::mul_zero()
+        } else {
+            T::mul_zero()
+        };
+        for i in 0..self.size() {
+            result = checked_mul(&result, unsafe { self.lu.get_unchecked(i, i) })?;
+        }
+        Ok(result)
+    }
+
+    /// Solve `A*x = b` for each col of `b`, returning solutions in cols of the result
+    ///
+    /// If `A` is singular, returns a [`MatError::NotInvertable`]
+    pub fn solve(&self, b: &dyn Mat<Item = T>) -> Result<DataMatrix<T>, MatError> {
+        let n = self.size();
+        if b.rows() != n {
+            return Err(InconsistentDimension {
+                need: (n, b.cols()),
+                got: b.dimensions(),
+            });
+        }
+        if self.is_singular() {
+            return Err(NotInvertable {
+                rank: self.rank()?,
+                rows: n,
+            });
+        }
+
+        let mut x = DataMatrix::zeros(n, b.cols());
+        unsafe {
+            for c in 0..b.cols() {
+                // forward substitution, `L*y = P*b`
+                for i in 0..n {
+                    let mut y = b.get_unchecked(self.perm[i], c).clone();
+                    for j in 0..i {
+                        y = checked_sub(&y, &checked_mul(self.lu.get_unchecked(i, j), x.get_unchecked(j, c))?)?;
+                    }
+                    *x.get_mut_unchecked(i, c) = y;
+                }
+                // back substitution, `U*x = y`
+                for i in (0..n).rev() {
+                    let mut y = x.get_unchecked(i, c).clone();
+                    for j in i + 1..n {
+                        y = checked_sub(&y, &checked_mul(self.lu.get_unchecked(i, j), x.get_unchecked(j, c))?)?;
+                    }
+                    *x.get_mut_unchecked(i, c) = checked_mul(&y, &self.lu.get_unchecked(i, i).clone().inv())?;
+                }
+            }
+        }
+
+        Ok(x)
+    }
+
+    /// The inverse of the factorized matrix, by solving against the identity
+    pub fn inv(&self) -> Result<DataMatrix<T>, MatError> {
+        self.solve(&DataMatrix::identity(self.size()))
+    }
+}
+
+/// LU decomposition of a square `mat` with partial pivoting, returning `(perm, L, U)`
+///
+/// Row `i` of `L*U` is row `perm[i]` of `mat`. Use [`LUDecomposition`] to keep the factorization
+/// for solving
+#[allow(clippy::type_complexity)]
+pub fn lu<T>(mat: &dyn Mat<Item = T>) -> Result<(Vec<usize>, DataMatrix<T>, DataMatrix<T>), MatError>
+where
+    T: LinearElem + Inv + Normal,
+{
+    let lu = LUDecomposition::new(mat)?;
+    let (l, u) = (lu.l(), lu.u());
+    Ok((lu.perm, l, u))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{rational, Rational};
+    use mat_macro::mat_;
+
+    #[test]
+    fn test_lu() {
+        let a: DataMatrix<Rational> = mat_![
+            0 1 2;
+            1 3 4;
+            2 8 9;
+        ]
+        .convert();
+
+        let (perm, l, u) = lu(&a).unwrap();
+        for i in 0..3 {
+            for j in 0..i {
+                assert_eq!(*u.get(i, j).unwrap(), rational!(0, 1));
+                assert_eq!(*l.get(j, i).unwrap(), rational!(0, 1));
+            }
+            assert_eq!(*l.get(i, i).unwrap(), rational!(1, 1));
+        }
+
+        let fact = LUDecomposition::new(&a).unwrap();
+        assert_eq!(fact.perm(), &perm[..]);
+        assert_eq!(fact.p().dot(&a).unwrap(), l.dot(&u).unwrap());
+        assert_eq!(fact.det().unwrap(), rational!(3, 1));
+    }
+
+    #[test]
+    fn test_lu_solve() {
+        let a: DataMatrix<Rational> = mat_![
+            1 2;
+            3 1;
+        ]
+        .convert();
+        let b: DataMatrix<Rational> = mat_![
+            1 0 5;
+            0 1 5;
+        ]
+        .convert();
+
+        let fact = LUDecomposition::new(&a).unwrap();
+        let x = fact.solve(&b).unwrap();
+        assert_eq!(a.dot(&x).unwrap(), b);
+        assert_eq!(
+            fact.inv().unwrap(),
+            mat_![
+                (rational!(-1, 5)) (rational!(2, 5));
+                (rational!(3, 5)) (rational!(-1, 5));
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lu_f64() {
+        let a: DataMatrix<f64> = mat_![
+            4.0 3.0;
+            6.0 3.0;
+        ];
+        let fact = LUDecomposition::new(&a).unwrap();
+        assert_eq!(fact.perm(), &[1, 0]);
+        assert!((fact.det().unwrap() + 6.0).abs() < 1e-9);
+
+        let x = fact.solve(&mat_![10.0; 12.0;]).unwrap();
+        assert_eq!(x, mat_![1.0; 2.0;]);
+    }
+
+    #[test]
+    fn test_lu_singular() {
+        let a: DataMatrix<Rational> = mat_![
+            0 1;
+            0 0;
+        ]
+        .convert();
+        let fact = LUDecomposition::new(&a).unwrap();
+        assert!(fact.is_singular());
+        assert_eq!(fact.det().unwrap(), rational!(0, 1));
+        assert!(matches!(
+            fact.solve(&DataMatrix::identity(2)),
+            Err(MatError::NotInvertable { rank: 1, rows: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_lu_scaled() {
+        // every element is far below the tolerance of `is_add_zero`
+        let a: DataMatrix<f64> = mat_![
+            0.0 1e-7;
+            1e-7 0.0;
+        ];
+        assert!((crate::alg::det(&a).unwrap() + 1e-14).abs() < 1e-28);
+
+        let a: DataMatrix<f64> = mat_![
+            2e-7 1e-7 0.0;
+            4e-7 3e-7 1e-7;
+            0.0 5e-7 6e-7;
+        ];
+        let fact = LUDecomposition::new(&a).unwrap();
+        assert!(!fact.is_singular());
+        let lu = fact.l().dot(&fact.u()).unwrap();
+        let pa = fact.p().dot(&a).unwrap();
+        for (x, y) in lu.iter().zip(pa.iter()) {
+            assert!((x - y).abs() < 1e-20);
+        }
+        let x = fact.solve(&mat_![3e-7; 8e-7; 11e-7;]).unwrap();
+        for xi in x.iter() {
+            assert!((xi - 1.0).abs() < 1e-12);
+        }
+
+        // singular, with exact arithmetic on powers of two
+        let s = 2f64.powi(-24);
+        let a: DataMatrix<f64> = mat_![
+            s (2.0 * s);
+            (2.0 * s) (4.0 * s);
+        ];
+        assert!(matches!(
+            LUDecomposition::new(&a).unwrap().solve(&DataMatrix::identity(2)),
+            Err(MatError::NotInvertable { rank: 1, rows: 2 })
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "big_rational")]
+    fn test_lu_tiny() {
+        // pivots whose `normal` underflows to `0.0`
+        let tiny = Rational::new(1.into(), num_bigint::BigInt::from(10).pow(400));
+        let a = DataMatrix::new(vec![tiny.clone(), rational!(0, 1), rational!(0, 1), tiny.clone()], 2, 2).unwrap();
+        let fact = LUDecomposition::new(&a).unwrap();
+        assert!(!fact.is_singular());
+        assert_eq!(fact.det().unwrap(), tiny.clone() * &tiny);
+        assert_eq!(fact.inv().unwrap().dot(&a).unwrap(), DataMatrix::identity(2));
+    }
+}
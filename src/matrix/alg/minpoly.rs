@@ -0,0 +1,120 @@
+use crate::element::LinearElem;
+use crate::error::MatError;
+use crate::matrix::{checked_dot, DataMatrix, EliminatedMatrix, Mat};
+use crate::{Polynomial, Rational};
+use MatError::*;
+
+/// Evaluate `p` at a square `mat`, that is `p(mat)`, by Horner's method with matrix products
+///
+/// The constant term is taken as a multiple of the identity. Fails with [`MatError::Overflow`]
+/// if intermediate results overflow
+pub fn eval_poly<T>(p: &Polynomial<T>, mat: &dyn Mat<Item = T>) -> Result<DataMatrix<T>, MatError>
+where
+    T: LinearElem,
+{
+    p.eval_mat(mat)
+}
+
+/// The minimal polynomial of a square `mat`, the monic polynomial `p` of least degree with
+/// `p(mat) = 0`
+///
+/// `I, A, A^2, ...` are flattened into cols of a matrix, which is eliminated until they are
+/// linearly dependent; the coefficients of the dependence are those of `p`. `mat` is
+/// diagonalizable iff `p` has no repeated roots
+pub fn minimal_polynomial(mat: &dyn Mat<Item = Rational>) -> Result<Polynomial<Rational>, MatError> {
+    if mat.dimensions() == (0, 0) {
+        return Err(EmptyMatrix);
+    }
+    if !mat.is_square() {
+        return Err(NotSquare {
+            dim: mat.dimensions(),
+        });
+    }
+
+    let n = mat.rows();
+    let mut powers = vec![DataMatrix::identity(n)];
+    // by Cayley-Hamilton, the powers are dependent once `A^n` is included
+    for k in 1..=n {
+        let next = unsafe { checked_dot(powers.last().unwrap(), mat)? };
+        powers.push(next);
+
+        let mut flat = DataMatrix::zeros(n * n, k + 1);
+        for (j, a) in powers.iter().enumerate() {
+            for r in 0..n * n {
+                unsafe { *flat.get_mut_unchecked(r, j) = a.get_unchecked(r / n, r % n).clone() }
+            }
+        }
+
+        let eliminated = EliminatedMatrix::eliminated(flat)?;
+        if eliminated.rank() <= k {
+            // the lower powers are independent, so the null space is one dimensional
+            let coef = eliminated.null_space()?.unwrap().data();
+            return Ok(Polynomial::new(coef).monic());
+        }
+    }
+
+    unreachable!("powers of a square are dependent by Cayley-Hamilton")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::alg::charpoly;
+    use crate::rational;
+    use mat_macro::mat_;
+
+    fn r(coef: &[i32]) -> Polynomial<Rational> {
+        Polynomial::new(coef.iter().map(|&x| rational!(x, 1)).collect())
+    }
+
+    #[test]
+    fn test_minimal_polynomial() {
+        // diagonalizable, with a repeated eigen value
+        let a: DataMatrix<Rational> = mat_![
+            1 0 0;
+            0 2 0;
+            0 0 1;
+        ]
+        .convert();
+        let p = minimal_polynomial(&a).unwrap();
+        assert_eq!(p, r(&[2, -3, 1]));
+        assert_eq!(eval_poly(&p, &a).unwrap(), DataMatrix::zeros(3, 3));
+
+        // not diagonalizable
+        let a: DataMatrix<Rational> = mat_![
+            2 1 0;
+            0 2 0;
+            0 0 2;
+        ]
+        .convert();
+        assert_eq!(minimal_polynomial(&a).unwrap(), r(&[4, -4, 1]));
+
+        let a: DataMatrix<Rational> = mat_![
+            1 2 0;
+            0 1 3;
+            4 0 2;
+        ]
+        .convert();
+        assert_eq!(minimal_polynomial(&a).unwrap(), charpoly(&a).unwrap());
+    }
+
+    #[test]
+    fn test_eval_poly() {
+        let a: DataMatrix<i64> = mat_![
+            1 2;
+            3 4;
+        ];
+        // Cayley-Hamilton
+        let p = charpoly(&a).unwrap();
+        assert_eq!(eval_poly(&p, &a).unwrap(), DataMatrix::zeros(2, 2));
+
+        let p = Polynomial::new(vec![1, 0, 1]);
+        assert_eq!(
+            eval_poly(&p, &a).unwrap(),
+            mat_![
+                8 10;
+                15 23;
+            ]
+        );
+    }
+}
@@ -1,26 +1,26 @@
 use crate::SliceMatrix;
-use crate::element::Inv;
+use crate::element::FloatElem;
 use crate::matrix::Mat;
 use crate::error::MatError;
 use MatError::*;
 
 /// Calculates the square of normal of the `j`th row of `mat`
-pub unsafe fn col_normal_sqr_unchecked(mat: &dyn Mat<Item = f64>, j: usize) -> f64 {
+pub unsafe fn col_normal_sqr_unchecked<T: FloatElem>(mat: &dyn Mat<Item = T>, j: usize) -> f64 {
     let mut sqr_sum = 0.0;
 
     for i in 0..mat.rows() {
-        sqr_sum += mat.get_unchecked(i, j).powi(2);
+        sqr_sum += mat.get_unchecked(i, j).normal().powi(2);
     }
 
     sqr_sum
 }
 
 /// Calculates the normal of the `j`th row of `mat`
-pub unsafe fn col_normal_unchecked(mat: &dyn Mat<Item = f64>, j: usize) -> f64 {
+pub unsafe fn col_normal_unchecked<T: FloatElem>(mat: &dyn Mat<Item = T>, j: usize) -> f64 {
     col_normal_sqr_unchecked(mat, j).sqrt()
 }
 
-pub fn col_normal(mat: &dyn Mat<Item = f64>, j: usize) -> Result<f64, MatError> {
+pub fn col_normal<T: FloatElem>(mat: &dyn Mat<Item = T>, j: usize) -> Result<f64, MatError> {
     if j >= mat.cols() {
         return Err(IndexError { dim: mat.dimensions(), i: 0, j, mutable: false });
     }
@@ -28,7 +28,7 @@ pub fn col_normal(mat: &dyn Mat<Item = f64>, j: usize) -> Result<f64, MatError>
     unsafe { Ok(col_normal_unchecked(mat, j)) }
 }
 
-pub fn col_normal_sqr(mat: &dyn Mat<Item = f64>, j: usize) -> Result<f64, MatError> {
+pub fn col_normal_sqr<T: FloatElem>(mat: &dyn Mat<Item = T>, j: usize) -> Result<f64, MatError> {
     if j >= mat.cols() {
         return Err(IndexError { dim: mat.dimensions(), i: 0, j, mutable: false });
     }
@@ -37,22 +37,22 @@ pub fn col_normal_sqr(mat: &dyn Mat<Item = f64>, j: usize) -> Result<f64, MatErr
 }
 
 /// Calculates the square of normal of the `i`th col of `mat`
-pub unsafe fn row_normal_sqr_unchecked(mat: &dyn Mat<Item = f64>, i: usize) -> f64 {
+pub unsafe fn row_normal_sqr_unchecked<T: FloatElem>(mat: &dyn Mat<Item = T>, i: usize) -> f64 {
     let mut sqr_sum = 0.0;
 
     for j in 0..mat.cols() {
-        sqr_sum += mat.get_unchecked(i, j).powi(2);
+        sqr_sum += mat.get_unchecked(i, j).normal().powi(2);
     }
 
     sqr_sum
 }
 
 /// Calculates the normal of the `j`th row of `mat`
-pub unsafe fn row_normal_unchecked(mat: &dyn Mat<Item = f64>, i: usize) -> f64 {
+pub unsafe fn row_normal_unchecked<T: FloatElem>(mat: &dyn Mat<Item = T>, i: usize) -> f64 {
     row_normal_sqr_unchecked(mat, i).sqrt()
 }
 
-pub fn row_normal(mat: &dyn Mat<Item = f64>, i: usize) -> Result<f64, MatError> {
+pub fn row_normal<T: FloatElem>(mat: &dyn Mat<Item = T>, i: usize) -> Result<f64, MatError> {
     if i >= mat.cols() {
         return Err(IndexError { dim: mat.dimensions(), i, j: 0, mutable: false });
     }
@@ -60,7 +60,7 @@ pub fn row_normal(mat: &dyn Mat<Item = f64>, i: usize) -> Result<f64, MatError>
     unsafe { Ok(row_normal_unchecked(mat, i)) }
 }
 
-pub fn row_normal_sqr(mat: &dyn Mat<Item = f64>, i: usize) -> Result<f64, MatError> {
+pub fn row_normal_sqr<T: FloatElem>(mat: &dyn Mat<Item = T>, i: usize) -> Result<f64, MatError> {
     if i >= mat.cols() {
         return Err(IndexError { dim: mat.dimensions(), i, j: 0, mutable: false });
     }
@@ -68,21 +68,21 @@ pub fn row_normal_sqr(mat: &dyn Mat<Item = f64>, i: usize) -> Result<f64, MatErr
     unsafe { Ok(row_normal_sqr_unchecked(mat, i)) }
 }
 
-pub fn normalize_cols(mat: &dyn Mat<Item = f64>) {
+pub fn normalize_cols<T: FloatElem>(mat: &dyn Mat<Item = T>) {
     for j in 0..mat.cols() {
         unsafe {
             let mut col = SliceMatrix::new_unchecked(mat, 0, mat.rows(), j, 1);
             let col_normal = col_normal_unchecked(mat, j);
-            col.scale(&col_normal.inv());
+            col.scale(&T::from_f64(col_normal.recip()));
         }
     }
 }
-pub fn normalize_rows(mat: &dyn Mat<Item = f64>) {
+pub fn normalize_rows<T: FloatElem>(mat: &dyn Mat<Item = T>) {
     for i in 0..mat.rows() {
         unsafe {
             let mut row = SliceMatrix::new_unchecked(mat, i, 1, 0, mat.cols());
             let row_normal = row_normal_unchecked(mat, i);
-            row.scale(&row_normal.inv());
+            row.scale(&T::from_f64(row_normal.recip()));
         }
     }
 }
@@ -37,9 +37,9 @@ mod test {
         let mut m: DataMatrix<Rational> = mat_![2 -1 14; -1 5 1; -3 1 9;].convert();
         orthogonalize(&mut m);
         assert_eq!(m.clone_data(), mat_![
-            (Rational(2, 1)) (Rational(3, 7)) (Rational(14, 1));
-            (Rational(-1, 1)) (Rational(30, 7)) (Rational(1, 1));
-            (Rational(-3, 1)) (Rational(-8, 7)) (Rational(9, 1));
+            (rational!(2, 1)) (rational!(3, 7)) (rational!(14, 1));
+            (rational!(-1, 1)) (rational!(30, 7)) (rational!(1, 1));
+            (rational!(-3, 1)) (rational!(-8, 7)) (rational!(9, 1));
         ].convert());
     }
 }
@@ -0,0 +1,117 @@
+use crate::error::MatError;
+use crate::matrix::{DataMatrix, Mat};
+use crate::Complex;
+use MatError::*;
+
+use super::eigen::{complex_qr_step, hessenberg_acc_unchecked};
+use super::EigenElem;
+
+/// Complex Schur decomposition of a square `mat`, returning `(Q, T)` where `mat = Q T Q^H`, `Q`
+/// is unitary and `T` is upper triangular with the eigen values on its diagnol
+///
+/// Real matrices are decomposed over the complex numbers too, so that `T` is always
+/// triangular. The Hessenberg form is iterated by the single-shift QR steps of
+/// [`EigenValueSolver`](super::EigenValueSolver) with Wilkinson shifts, and if some eigen value
+/// doesn't converge in `30` steps, returns [`MatError::NotConverged`]
+#[allow(clippy::type_complexity)]
+pub fn schur<T: EigenElem>(mat: &dyn Mat<Item = T>) -> Result<(DataMatrix<Complex>, DataMatrix<Complex>), MatError> {
+    if !mat.is_square() {
+        return Err(NotSquare {
+            dim: mat.dimensions(),
+        });
+    }
+
+    let n = mat.rows();
+    let mut h = DataMatrix::zeros(n, n);
+    let mut q = DataMatrix::identity(n);
+    let mut iterations = 0;
+    unsafe {
+        for i in 0..n {
+            for j in 0..n {
+                *h.get_mut_unchecked(i, j) = mat.get_unchecked(i, j).clone().into();
+            }
+        }
+        hessenberg_acc_unchecked(&mut h, Some(&mut q));
+
+        let mut hi = n;
+        let mut steps = 0;
+        while hi > 1 {
+            // find the unreduced block `l..hi`, deflating negligible subdiagnol elements
+            let mut l = hi - 1;
+            while l > 0 {
+                let s = h.get_unchecked(l - 1, l - 1).normal() + h.get_unchecked(l, l).normal();
+                if h.get_unchecked(l, l - 1).normal() <= f64::EPSILON * s {
+                    *h.get_mut_unchecked(l, l - 1) = Complex(0.0, 0.0);
+                    break;
+                }
+                l -= 1;
+            }
+            if l == hi - 1 {
+                hi -= 1;
+                steps = 0;
+                continue;
+            }
+            if steps == 30 {
+                return Err(NotConverged { iterations });
+            }
+            steps += 1;
+            iterations += 1;
+
+            // Wilkinson shift, with an exceptional one every 10 steps
+            complex_qr_step(&mut h, l, hi - 1, steps % 10 == 0, Some(&mut q));
+        }
+    }
+
+    Ok((q, h))
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::eigen::adjoint;
+    use super::*;
+    use mat_macro::mat_;
+
+    fn check_schur(a: &DataMatrix<Complex>) {
+        let (q, t) = schur(a).unwrap();
+        let n = a.rows();
+        for i in 0..n {
+            for j in 0..i {
+                assert_eq!(*t.get(i, j).unwrap(), Complex(0.0, 0.0));
+            }
+        }
+        let qtq = q.dot(&t).unwrap().dot(&adjoint(&q)).unwrap();
+        let qq = adjoint(&q).dot(&q).unwrap();
+        let id = DataMatrix::<Complex>::identity(n);
+        for i in 0..n {
+            for j in 0..n {
+                assert!((*qtq.get(i, j).unwrap() - a.get(i, j).unwrap()).normal() < 1e-12);
+                assert!((*qq.get(i, j).unwrap() - id.get(i, j).unwrap()).normal() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_schur() {
+        // eigen values `±i` and `2`
+        let a: DataMatrix<f64> = mat_![
+            0.0 (-1.0) 3.0;
+            1.0 0.0 2.0;
+            0.0 0.0 2.0;
+        ];
+        let (_, t) = schur(&a).unwrap();
+        let mut diag: Vec<Complex> = (0..3).map(|i| *t.get(i, i).unwrap()).collect();
+        diag.sort_by(|x, y| x.1.total_cmp(&y.1));
+        for (x, y) in diag.iter().zip([Complex(0.0, -1.0), Complex(2.0, 0.0), Complex(0.0, 1.0)]) {
+            assert!((*x - &y).normal() < 1e-12);
+        }
+        check_schur(&a.convert());
+
+        let a = mat_![
+            (Complex(1.0, 2.0)) (Complex(0.0, 1.0)) (Complex(3.0, 0.0)) (Complex(1.0, 0.0));
+            (Complex(2.0, 0.0)) (Complex(-1.0, 0.0)) (Complex(0.0, -1.0)) (Complex(0.0, 0.0));
+            (Complex(0.0, 0.0)) (Complex(4.0, 1.0)) (Complex(2.0, 0.0)) (Complex(1.0, 1.0));
+            (Complex(1.0, 0.0)) (Complex(0.0, 0.0)) (Complex(5.0, 0.0)) (Complex(0.0, 3.0));
+        ];
+        check_schur(&a);
+    }
+}
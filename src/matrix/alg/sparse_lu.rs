@@ -0,0 +1,406 @@
+use super::super::eliminated_matrix::{checked_mul, checked_sub};
+use crate::element::{Inv, LinearElem, Normal};
+use crate::error::MatError;
+use crate::matrix::{DataMatrix, Mat, SparseMatrix};
+use MatError::*;
+
+/// A pivot is accepted if it's at least this fraction of the largest candidate in its col, so
+/// that a sparser row can be preferred without losing much stability
+const PIVOT_THRESHOLD: f64 = 0.1;
+
+/// A sparse row, as `(col, value)` with increasing cols
+type Row<T> = Vec<(usize, T)>;
+
+/// Reverse Cuthill-McKee ordering of the pattern of `a + a^T`, which gathers non-zeros near
+/// the diagnol, and so limits fill-in of elimination to the band
+fn reverse_cuthill_mckee<T: LinearElem>(a: &SparseMatrix<T>) -> Vec<usize> {
+    let n = a.rows();
+    let mut adjacent: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for (i, j, _) in a.iter_nonzero() {
+        if i != j {
+            adjacent[i].push(j);
+            adjacent[j].push(i);
+        }
+    }
+    for v in adjacent.iter_mut() {
+        v.sort_unstable();
+        v.dedup();
+    }
+
+    let mut order = Vec::with_capacity(n);
+    let mut visited = vec![false; n];
+    let mut by_degree: Vec<usize> = (0..n).collect();
+    by_degree.sort_by_key(|&i| adjacent[i].len());
+    // breadth first from a node of least degree in each component, visiting neighbours by
+    // increasing degree
+    for &start in by_degree.iter() {
+        if visited[start] {
+            continue;
+        }
+        visited[start] = true;
+        let mut head = order.len();
+        order.push(start);
+        while head < order.len() {
+            let mut next: Vec<usize> = adjacent[order[head]].iter().copied().filter(|&j| !visited[j]).collect();
+            next.sort_by_key(|&j| adjacent[j].len());
+            for j in next {
+                visited[j] = true;
+                order.push(j);
+            }
+            head += 1;
+        }
+    }
+    order.reverse();
+    order
+}
+
+/// Parity of a permutation, `true` if odd
+fn is_odd(perm: &[usize]) -> bool {
+    let mut visited = vec![false; perm.len()];
+    let mut odd = false;
+    for i in 0..perm.len() {
+        let mut j = i;
+        let mut len = 0;
+        while !visited[j] {
+            visited[j] = true;
+            j = perm[j];
+            len += 1;
+        }
+        if len > 0 && len % 2 == 0 {
+            odd = !odd;
+        }
+    }
+    odd
+}
+
+/// LU decomposition of a [`SparseMatrix`], `P*A*Q = L*U`, keeping the factors sparse
+///
+/// Cols are ordered by reverse Cuthill-McKee to keep the non-zeros within a narrow band, and
+/// each pivot is the one of the fewest non-zeros in its row among those not much smaller than
+/// the largest of the col. This limits fill-in, so that for example a finite-difference system
+/// on a `100x100` grid factorizes in fewer than `n * 150` non-zeros, rather than `n^2`.
+pub struct SparseLUDecomposition<T>
+where
+    T: LinearElem,
+{
+    /// Row `k` of `L` below the diagnol, as `(col, factor)`
+    l: Vec<Row<T>>,
+    /// Row `k` of `U` from the diagnol on, where the pivot comes first. Empty if no pivot is
+    /// found for col `k`
+    u: Vec<Row<T>>,
+    /// Row `k` of `P*A*Q` is row `row_perm[k]` of `A`
+    row_perm: Vec<usize>,
+    /// Col `k` of `P*A*Q` is col `col_perm[k]` of `A`
+    col_perm: Vec<usize>,
+}
+
+impl<T> SparseLUDecomposition<T>
+where
+    T: LinearElem + Inv + Normal,
+{
+    /// Factorize a square `mat`
+    ///
+    /// Like [`LUDecomposition`](super::LUDecomposition), singular matrices can be factorized
+    /// too, but then [`solve`](SparseLUDecomposition::solve) fails. Fails with
+    /// [`MatError::Overflow`] if the elements overflow
+    pub fn new(mat: &SparseMatrix<T>) -> Result<Self, MatError> {
+        if !mat.is_square() {
+            return Err(NotSquare {
+                dim: mat.dimensions(),
+            });
+        }
+
+        let n = mat.rows();
+        let col_perm = reverse_cuthill_mckee(mat);
+        let mut col_pos = vec![0; n];
+        for (k, &j) in col_perm.iter().enumerate() {
+            col_pos[j] = k;
+        }
+
+        // the active rows in the permuted cols, and for each col the rows that may have it
+        let mut rows: Vec<Row<T>> = vec![Vec::new(); n];
+        let mut col_rows: Vec<Vec<usize>> = vec![Vec::new(); n];
+        for (i, j, x) in mat.iter_nonzero() {
+            rows[i].push((col_pos[j], x.clone()));
+            col_rows[col_pos[j]].push(i);
+        }
+        for row in rows.iter_mut() {
+            row.sort_by_key(|(j, _)| *j);
+        }
+
+        let mut l: Vec<Row<T>> = vec![Vec::new(); n];
+        let mut u: Vec<Row<T>> = Vec::with_capacity(n);
+        let mut pivot_of = vec![None; n];
+        let mut done = vec![false; n];
+        for k in 0..n {
+            // all cols before `k` are eliminated, so rows with col `k` have it first
+            let mut candidates = std::mem::take(&mut col_rows[k]);
+            candidates.sort_unstable();
+            candidates.dedup();
+            candidates.retain(|&i| !done[i] && rows[i].first().is_some_and(|(j, _)| *j == k));
+
+            let largest = candidates.iter().map(|&i| rows[i][0].1.normal()).fold(0.0, f64::max);
+            let pivot = candidates
+                .iter()
+                .copied()
+                .filter(|&i| rows[i][0].1.normal() >= PIVOT_THRESHOLD * largest)
+                .min_by(|&a, &b| {
+                    rows[a].len().cmp(&rows[b].len()).then(rows[b][0].1.normal().total_cmp(&rows[a][0].1.normal()))
+                });
+            // `normal` may underflow for tiny non-zero elements, so it only ranks the candidates,
+            // and only exact zeros are rejected, as `is_add_zero` of floats has a tolerance
+            let p = match pivot {
+                Some(p) if rows[p][0].1 != T::add_zero() => p,
+                _ => {
+                    u.push(Vec::new());
+                    continue;
+                }
+            };
+            done[p] = true;
+            pivot_of[k] = Some(p);
+            let pivot_row = std::mem::take(&mut rows[p]);
+            let pivot_inv = pivot_row[0].1.clone().inv();
+
+            for &i in candidates.iter().filter(|&&i| i != p) {
+                let factor = checked_mul(&rows[i][0].1, &pivot_inv)?;
+                l[i].push((k, factor.clone()));
+
+                // `row_i -= factor * pivot_row`, merging the patterns
+                let old = std::mem::take(&mut rows[i]);
+                let mut merged = Vec::with_capacity(old.len() + pivot_row.len());
+                let (mut a, mut b) = (old.into_iter().skip(1).peekable(), pivot_row[1..].iter().peekable());
+                loop {
+                    let (j, x) = match (a.peek(), b.peek()) {
+                        (Some((ja, _)), Some((jb, _))) if ja == jb => {
+                            let (j, x) = a.next().unwrap();
+                            let y = &b.next().unwrap().1;
+                            (j, checked_sub(&x, &checked_mul(&factor, y)?)?)
+                        }
+                        (Some((ja, _)), jb) if jb.is_none_or(|(jb, _)| ja < jb) => a.next().unwrap(),
+                        (_, Some((jb, y))) => {
+                            // fill-in
+                            let (j, x) = (*jb, checked_sub(&T::add_zero(), &checked_mul(&factor, y)?)?);
+                            b.next();
+                            col_rows[j].push(i);
+                            (j, x)
+                        }
+                        _ => break,
+                    };
+                    // only exact zeros, as `is_add_zero` of floats has a tolerance
+                    if x != T::add_zero() {
+                        merged.push((j, x));
+                    }
+                }
+                rows[i] = merged;
+            }
+            u.push(pivot_row);
+        }
+
+        // rows left without pivots go to the cols without pivots
+        let mut rest = (0..n).filter(|&i| !done[i]);
+        let row_perm: Vec<usize> = pivot_of.into_iter().map(|p| p.unwrap_or_else(|| rest.next().unwrap())).collect();
+        let l = (0..n).map(|k| std::mem::take(&mut l[row_perm[k]])).collect();
+
+        Ok(SparseLUDecomposition { l, u, row_perm, col_perm })
+    }
+
+    /// Dimension of the factorized square
+    pub fn size(&self) -> usize {
+        self.u.len()
+    }
+
+    /// Row `k` of `P*A*Q` is row `row_perm()[k]` of `A`
+    pub fn row_perm(&self) -> &[usize] {
+        &self.row_perm
+    }
+
+    /// Col `k` of `P*A*Q` is col `col_perm()[k]` of `A`
+    pub fn col_perm(&self) -> &[usize] {
+        &self.col_perm
+    }
+
+    /// Number of non-zeros in `L` and `U` together, showing how much fill-in there is
+    pub fn nnz(&self) -> usize {
+        self.l.iter().chain(self.u.iter()).map(|r| r.len()).sum()
+    }
+
+    /// Number of pivots found, which is the rank for exact elements
+    fn pivots(&self) -> usize {
+        self.u.iter().filter(|r| !r.is_empty()).count()
+    }
+
+    /// If the factorized matrix is singular, that is some col has no pivot
+    pub fn is_singular(&self) -> bool {
+        self.pivots() < self.size()
+    }
+
+    /// Determinant of the factorized matrix
+    pub fn det(&self) -> Result<T, MatError> {
+        if self.is_singular() {
+            return Ok(T::add_zero());
+        }
+        let mut result = if is_odd(&self.row_perm) != is_odd(&self.col_perm) {
+            T::add_zero() - &T::mul_zero()
+        } else {
+            T::mul_zero()
+        };
+        for row in self.u.iter() {
+            result = checked_mul(&result, &row[0].1)?;
+        }
+        Ok(result)
+    }
+
+    /// Solve `A*x = b` for each col of `b`, returning solutions in cols of the result
+    ///
+    /// If `A` is singular, returns a [`MatError::NotInvertable`]
+    pub fn solve(&self, b: &dyn Mat<Item = T>) -> Result<DataMatrix<T>, MatError> {
+        let n = self.size();
+        if b.rows() != n {
+            return Err(InconsistentDimension {
+                need: (n, b.cols()),
+                got: b.dimensions(),
+            });
+        }
+        if self.is_singular() {
+            return Err(NotInvertable {
+                rank: self.pivots(),
+                rows: n,
+            });
+        }
+
+        let mut x = DataMatrix::zeros(n, b.cols());
+        let mut y = vec![T::add_zero(); n];
+        for c in 0..b.cols() {
+            // forward substitution, `L*y = P*b`
+            for k in 0..n {
+                let mut v = unsafe { b.get_unchecked(self.row_perm[k], c) }.clone();
+                for (j, f) in self.l[k].iter() {
+                    v = checked_sub(&v, &checked_mul(f, &y[*j])?)?;
+                }
+                y[k] = v;
+            }
+            // back substitution, `U*z = y` where `x = Q*z`
+            for k in (0..n).rev() {
+                let mut v = y[k].clone();
+                for (j, u) in self.u[k][1..].iter() {
+                    v = checked_sub(&v, &checked_mul(u, &y[*j])?)?;
+                }
+                y[k] = checked_mul(&v, &self.u[k][0].1.clone().inv())?;
+            }
+            for (k, &j) in self.col_perm.iter().enumerate() {
+                unsafe { *x.get_mut_unchecked(j, c) = y[k].clone() }
+            }
+        }
+
+        Ok(x)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{rational, Rational};
+    use mat_macro::mat_;
+
+    /// 5-point laplacian on an `m` by `m` grid, numbered row by row
+    fn laplacian(m: usize) -> SparseMatrix<f64> {
+        let mut triplets = Vec::new();
+        for r in 0..m {
+            for c in 0..m {
+                let i = r * m + c;
+                triplets.push((i, i, 4.0));
+                if r > 0 {
+                    triplets.push((i, i - m, -1.0));
+                }
+                if r + 1 < m {
+                    triplets.push((i, i + m, -1.0));
+                }
+                if c > 0 {
+                    triplets.push((i, i - 1, -1.0));
+                }
+                if c + 1 < m {
+                    triplets.push((i, i + 1, -1.0));
+                }
+            }
+        }
+        SparseMatrix::from_triplets(m * m, m * m, triplets).unwrap()
+    }
+
+    #[test]
+    fn test_sparse_lu() {
+        let m = 30;
+        let a = laplacian(m);
+        let lu = SparseLUDecomposition::new(&a).unwrap();
+        // the band is about `m` wide, so `L` and `U` each take about `m` per row
+        assert!(lu.nnz() < 3 * m * m * m, "{}", lu.nnz());
+
+        let x: DataMatrix<f64> = DataMatrix::one_col((0..m * m).map(|i| (i % 7) as f64 - 3.0).collect());
+        let b = a.dot(&x).unwrap();
+        let solved = lu.solve(&b).unwrap();
+        for i in 0..m * m {
+            assert!((solved.get(i, 0).unwrap() - x.get(i, 0).unwrap()).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_sparse_lu_scaled() {
+        // a nonsingular tridiagonal system, with every element below the tolerance of `is_add_zero`
+        let n = 50;
+        let mut triplets = Vec::new();
+        for i in 0..n {
+            triplets.push((i, i, 4e-7));
+            if i > 0 {
+                triplets.push((i, i - 1, -1e-7));
+                triplets.push((i - 1, i, -1e-7));
+            }
+        }
+        let a = SparseMatrix::from_triplets(n, n, triplets).unwrap();
+        let lu = SparseLUDecomposition::new(&a).unwrap();
+        assert!(!lu.is_singular());
+
+        let x: DataMatrix<f64> = DataMatrix::one_col((0..n).map(|i| (i % 5) as f64 - 2.0).collect());
+        let solved = lu.solve(&a.dot(&x).unwrap()).unwrap();
+        for i in 0..n {
+            assert!((solved.get(i, 0).unwrap() - x.get(i, 0).unwrap()).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_sparse_lu_exact() {
+        // needs row exchanges, as `a[0, 0]` is zero
+        let dense: DataMatrix<Rational> = mat_![
+            0 2 0 1;
+            3 0 0 0;
+            0 1 4 0;
+            1 0 2 5;
+        ]
+        .convert();
+        let a = SparseMatrix::from_dense(&dense);
+        let lu = SparseLUDecomposition::new(&a).unwrap();
+        assert_eq!(lu.det().unwrap(), crate::alg::det(&dense).unwrap());
+
+        let b: DataMatrix<Rational> = mat_![1; 2; 3; 4;].convert();
+        let x = lu.solve(&b).unwrap();
+        assert_eq!(dense.dot(&x).unwrap(), b);
+
+        let singular: DataMatrix<Rational> = mat_![
+            1 2;
+            2 4;
+        ]
+        .convert();
+        let lu = SparseLUDecomposition::new(&SparseMatrix::from_dense(&singular)).unwrap();
+        assert_eq!(lu.det().unwrap(), rational!(0, 1));
+        assert!(matches!(lu.solve(&mat_![(rational!(1, 1)); (rational!(1, 1));]), Err(NotInvertable { rank: 1, .. })));
+    }
+
+    #[test]
+    #[cfg(feature = "big_rational")]
+    fn test_sparse_lu_tiny() {
+        // elements whose `normal` underflows to `0.0`
+        let tiny = Rational::new(1.into(), num_bigint::BigInt::from(10).pow(400));
+        let a = SparseMatrix::from_triplets(2, 2, vec![(0, 1, tiny.clone()), (1, 0, tiny.clone())]).unwrap();
+        let lu = SparseLUDecomposition::new(&a).unwrap();
+        assert!(!lu.is_singular());
+        assert_eq!(lu.det().unwrap(), rational!(0, 1) - &(tiny.clone() * &tiny));
+    }
+}
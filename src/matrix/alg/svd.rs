@@ -0,0 +1,394 @@
+use crate::element::FloatElem;
+use crate::error::MatError;
+use crate::{matrix::Mat, DataMatrix, SliceMatrix};
+
+use super::eigen::{adjoint, phase};
+use super::{col_normal_sqr_unchecked, householder_unchecked};
+
+/// Rotate cols `i` and `j` of `m`, so that `col_i = c*col_i + s*col_j`
+/// and `col_j = -s*col_i + c*col_j`
+unsafe fn rotate_cols<T: FloatElem>(m: &mut DataMatrix<T>, i: usize, j: usize, c: f64, s: f64) {
+    let (c, s) = (T::from_f64(c), T::from_f64(s));
+    for r in 0..m.rows() {
+        let (x, y) = (m.get_unchecked(r, i).clone(), m.get_unchecked(r, j).clone());
+        *m.get_mut_unchecked(r, i) = c.clone() * &x + &(s.clone() * &y);
+        *m.get_mut_unchecked(r, j) = c.clone() * &y - &(s.clone() * &x);
+    }
+}
+
+/// Multiply col `j` of `m` by `x`
+unsafe fn scale_col<T: FloatElem>(m: &mut DataMatrix<T>, j: usize, x: &T) {
+    for r in 0..m.rows() {
+        *m.get_mut_unchecked(r, j) *= x;
+    }
+}
+
+/// `(c, s, r)` such that `[c s; -s c] * [y; z] = [r; 0]`
+fn givens(y: f64, z: f64) -> (f64, f64, f64) {
+    let r = y.hypot(z);
+    if r == 0.0 {
+        (1.0, 0.0, 0.0)
+    } else {
+        (y / r, z / r, r)
+    }
+}
+
+/// Golub-Kahan bidiagonalization of `a`, which must have `rows >= cols`
+///
+/// `a` is transformed into upper bidiagonal `B` and `(U, V)` with `A = U * B * V^H` returned
+unsafe fn bidiagonalize<T: FloatElem>(a: &mut DataMatrix<T>) -> (DataMatrix<T>, DataMatrix<T>) {
+    let (m, n) = a.dimensions();
+    let u = DataMatrix::identity(m);
+    let v = DataMatrix::identity(n);
+
+    for k in 0..n {
+        // zero the col below `a[k, k]`
+        if k + 1 < m {
+            let x = SliceMatrix::new_unchecked(a, k, m - k, k, 1);
+            if col_normal_sqr_unchecked(&x, 0) != 0.0 {
+                let h = householder_unchecked(&x);
+
+                let mut d = SliceMatrix::new_unchecked(a, k, m - k, k, n - k);
+                let hd = h.dot_unchecked(&d);
+                d.write_data_unchecked(&hd);
+
+                let mut b = SliceMatrix::new_unchecked(&u, 0, m, k, m - k);
+                let bh = b.dot_unchecked(&h);
+                b.write_data_unchecked(&bh);
+            }
+        }
+
+        // zero the row right of `a[k, k + 1]`, by the reflector of its conjugate since the
+        // reflector is hermitian
+        if k + 2 < n {
+            let x = adjoint(&SliceMatrix::new_unchecked(a, k, 1, k + 1, n - k - 1));
+            if col_normal_sqr_unchecked(&x, 0) != 0.0 {
+                let h = householder_unchecked(&x);
+
+                let mut d = SliceMatrix::new_unchecked(a, k, m - k, k + 1, n - k - 1);
+                let dh = d.dot_unchecked(&h);
+                d.write_data_unchecked(&dh);
+
+                let mut b = SliceMatrix::new_unchecked(&v, 0, n, k + 1, n - k - 1);
+                let bh = b.dot_unchecked(&h);
+                b.write_data_unchecked(&bh);
+            }
+        }
+    }
+
+    (u, v)
+}
+
+/// One implicit-shift QR step on the unreduced block `lo..=hi` of the bidiagonal `(d, e)`
+unsafe fn golub_kahan_step<T: FloatElem>(
+    d: &mut [f64],
+    e: &mut [f64],
+    lo: usize,
+    hi: usize,
+    u: &mut DataMatrix<T>,
+    v: &mut DataMatrix<T>,
+) {
+    // Wilkinson shift from the trailing 2x2 of `B^T * B`
+    let t11 = d[hi - 1].powi(2) + if hi - 1 > lo { e[hi - 2].powi(2) } else { 0.0 };
+    let t12 = d[hi - 1] * e[hi - 1];
+    let t22 = d[hi].powi(2) + e[hi - 1].powi(2);
+    let delta = (t11 - t22) / 2.0;
+    let sign = if delta >= 0.0 { 1.0 } else { -1.0 };
+    let mu = t22 - t12.powi(2) / (delta + sign * delta.hypot(t12));
+
+    let mut y = d[lo].powi(2) - mu;
+    let mut z = d[lo] * e[lo];
+
+    for k in lo..hi {
+        // rotate cols `k` and `k + 1`, chasing the bulge to `(k + 1, k)`
+        let (c, s, r) = givens(y, z);
+        if k > lo {
+            e[k - 1] = r;
+        }
+        let (dk, ek) = (d[k], e[k]);
+        d[k] = c * dk + s * ek;
+        e[k] = -s * dk + c * ek;
+        let bulge = s * d[k + 1];
+        d[k + 1] *= c;
+        rotate_cols(v, k, k + 1, c, s);
+
+        // rotate rows `k` and `k + 1`, chasing the bulge to `(k, k + 2)`
+        let (c, s, r) = givens(d[k], bulge);
+        d[k] = r;
+        let (ek, dk1) = (e[k], d[k + 1]);
+        e[k] = c * ek + s * dk1;
+        d[k + 1] = -s * ek + c * dk1;
+        rotate_cols(u, k, k + 1, c, s);
+
+        if k + 1 < hi {
+            y = e[k];
+            z = s * e[k + 1];
+            e[k + 1] *= c;
+        }
+    }
+}
+
+/// With `d[i]` being zero, zero `e[i]` by rotating rows `i` and `j` for `j` in `i+1..=hi`
+unsafe fn chase_zero_diag<T: FloatElem>(d: &mut [f64], e: &mut [f64], i: usize, hi: usize, u: &mut DataMatrix<T>) {
+    let mut f = e[i];
+    e[i] = 0.0;
+    for j in i + 1..=hi {
+        let (c, s, r) = givens(d[j], f);
+        d[j] = r;
+        rotate_cols(u, j, i, c, s);
+        if j < hi {
+            f = -s * e[j];
+            e[j] *= c;
+        }
+    }
+}
+
+/// Implementation of [`svd`] for `rows >= cols`, failing if the bidiagonal isn't diagonalized
+/// in `max_iter` steps
+#[allow(clippy::type_complexity)]
+unsafe fn svd_tall<T: FloatElem>(
+    mat: &dyn Mat<Item = T>,
+    max_iter: usize,
+) -> Result<(DataMatrix<T>, Vec<f64>, DataMatrix<T>), MatError> {
+    let n = mat.cols();
+    let mut a = mat.clone_data();
+    let (mut u, mut v) = bidiagonalize(&mut a);
+
+    // make the bidiagonal real and non-negative, moving the phases of its elements into the
+    // cols of `U` and `V`
+    let mut d = Vec::with_capacity(n);
+    let mut e = Vec::with_capacity(n - 1);
+    let mut q = T::mul_zero();
+    for k in 0..n {
+        let dk = a.get_unchecked(k, k).clone() * &q;
+        let p = phase(&dk);
+        scale_col(&mut u, k, &p);
+        d.push(dk.normal());
+        if k + 1 < n {
+            let ek = p.conj() * a.get_unchecked(k, k + 1);
+            q = phase(&ek).conj();
+            scale_col(&mut v, k + 1, &q);
+            e.push(ek.normal());
+        }
+    }
+
+    let norm = (0..n)
+        .map(|i| d[i].abs() + if i + 1 < n { e[i].abs() } else { 0.0 })
+        .fold(0.0, f64::max);
+    let eps = f64::EPSILON;
+
+    for iter in 0..=max_iter {
+        for i in 0..n - 1 {
+            if e[i].abs() <= eps * (d[i].abs() + d[i + 1].abs()) {
+                e[i] = 0.0;
+            }
+        }
+        for di in d.iter_mut() {
+            if di.abs() <= eps * norm {
+                *di = 0.0;
+            }
+        }
+
+        // find the last unreduced block `lo..=hi`
+        let mut hi = n - 1;
+        while hi > 0 && e[hi - 1] == 0.0 {
+            hi -= 1;
+        }
+        if hi == 0 {
+            break;
+        }
+        if iter == max_iter {
+            return Err(MatError::NotConverged { iterations: max_iter });
+        }
+        let mut lo = hi - 1;
+        while lo > 0 && e[lo - 1] != 0.0 {
+            lo -= 1;
+        }
+
+        match (lo..hi).find(|&i| d[i] == 0.0) {
+            Some(i) => chase_zero_diag(&mut d, &mut e, i, hi, &mut u),
+            None => golub_kahan_step(&mut d, &mut e, lo, hi, &mut u, &mut v),
+        }
+    }
+
+    // make singular values non-negative and sort them in descending order
+    for (i, di) in d.iter_mut().enumerate() {
+        if *di < 0.0 {
+            *di = -*di;
+            scale_col(&mut v, i, &T::from_f64(-1.0));
+        }
+    }
+    for i in 0..n {
+        let k = (i..n).max_by(|&x, &y| d[x].total_cmp(&d[y])).unwrap();
+        if k != i {
+            d.swap(i, k);
+            u.col_unchecked(i).swap_unchecked(&mut u.col_unchecked(k));
+            v.col_unchecked(i).swap_unchecked(&mut v.col_unchecked(k));
+        }
+    }
+
+    Ok((u, d, v))
+}
+
+/// Singular value decomposition `A = U * Σ * V^H` of a `(m, n)` matrix, returning `(U, Σ, V^H)`
+///
+/// `U` is `(m, m)` and `V^H` is `(n, n)`, both unitary (orthogonal for real matrices). `Σ` is
+/// `(m, n)`, with the singular values on the diagnol in descending order.
+///
+/// The matrix is bidiagonalized by Householder transformations, and for complex matrices the
+/// bidiagonal is made real by scaling rows and cols with phases. Then it's diagonalized by
+/// implicit-shift QR iterations. If that doesn't converge, returns [`MatError::NotConverged`]
+#[allow(clippy::type_complexity)]
+pub fn svd<T: FloatElem>(
+    mat: &dyn Mat<Item = T>,
+) -> Result<(DataMatrix<T>, DataMatrix<T>, DataMatrix<T>), MatError> {
+    let (m, n) = mat.dimensions();
+    if m == 0 || n == 0 {
+        return Err(MatError::EmptyMatrix);
+    }
+
+    // each singular value takes a few iterations in practice
+    let max_iter = 75 * m.max(n);
+
+    unsafe {
+        if m >= n {
+            let (u, d, v) = svd_tall(mat, max_iter)?;
+            let mut sigma = DataMatrix::zeros(m, n);
+            for (i, di) in d.into_iter().enumerate() {
+                *sigma.get_mut_unchecked(i, i) = T::from_f64(di);
+            }
+            Ok((u, sigma, adjoint(&v)))
+        } else {
+            // `A^H = U' * Σ' * V'^H` gives `A = V' * Σ'^T * U'^H`
+            let t = adjoint(mat);
+            let (u, d, v) = svd_tall(&t, max_iter)?;
+            let mut sigma = DataMatrix::zeros(m, n);
+            for (i, di) in d.into_iter().enumerate() {
+                *sigma.get_mut_unchecked(i, i) = T::from_f64(di);
+            }
+            Ok((v, sigma, adjoint(&u)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Complex;
+    use mat_macro::mat_;
+
+    fn assert_close(a: &dyn Mat<Item = f64>, b: &dyn Mat<Item = f64>) {
+        assert_eq!(a.dimensions(), b.dimensions());
+        for i in 0..a.rows() {
+            for j in 0..a.cols() {
+                assert!((a.get(i, j).unwrap() - b.get(i, j).unwrap()).abs() < 1e-9);
+            }
+        }
+    }
+
+    fn check_svd(a: &DataMatrix<f64>) -> Vec<f64> {
+        let (u, sigma, vt) = svd(a).unwrap();
+        let (m, n) = a.dimensions();
+
+        assert_close(&u.dot(&sigma).unwrap().dot(&vt).unwrap(), a);
+        assert_close(&u.clone_data().transposed().dot(&u).unwrap(), &DataMatrix::identity(m));
+        assert_close(&vt.dot(&vt.clone_data().transposed()).unwrap(), &DataMatrix::identity(n));
+
+        let s: Vec<f64> = (0..m.min(n)).map(|i| *sigma.get(i, i).unwrap()).collect();
+        for w in s.windows(2) {
+            assert!(w[0] >= w[1] && w[1] >= 0.0);
+        }
+        s
+    }
+
+    #[test]
+    fn test_svd_wide() {
+        let a = mat_![
+            3.0 2.0 2.0;
+            2.0 3.0 (-2.0);
+        ];
+        let s = check_svd(&a);
+        assert!((s[0] - 5.0).abs() < 1e-9);
+        assert!((s[1] - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_svd_rank_deficient() {
+        let a = mat_![
+            1.0 2.0;
+            2.0 4.0;
+            3.0 6.0;
+        ];
+        let s = check_svd(&a);
+        assert!((s[0] - 70f64.sqrt()).abs() < 1e-9);
+        assert!(s[1].abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_svd_square() {
+        let a = mat_![
+            4.0 (-1.0) 2.0 0.5;
+            1.0 3.0 0.0 (-2.0);
+            0.0 2.0 5.0 1.0;
+            (-3.0) 1.0 1.0 2.0;
+        ];
+        check_svd(&a);
+
+        let a = mat_![
+            0.0 1.0 0.0;
+            0.0 0.0 1.0;
+            0.0 0.0 0.0;
+        ];
+        assert_eq!(check_svd(&a), vec![1.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_svd_small_scale() {
+        let mut a = mat_![
+            1.0 2.0 3.0;
+            4.0 5.0 6.0;
+            7.0 8.0 10.0;
+        ];
+        a.scale(&1e-5);
+        check_svd(&a);
+    }
+
+    #[test]
+    fn test_svd_complex() {
+        let a: DataMatrix<Complex> = mat_![
+            (Complex(1.0, 2.0)) (Complex(0.0, -1.0));
+            (Complex(3.0, 0.0)) (Complex(2.0, 1.0));
+            (Complex(0.0, 1.0)) (Complex(-1.0, 4.0));
+        ];
+        for a in [a.clone_data(), adjoint(&a)] {
+            let (u, sigma, vh) = svd(&a).unwrap();
+            let (m, n) = a.dimensions();
+            let close = |x: &DataMatrix<Complex>, y: &DataMatrix<Complex>| {
+                assert_eq!(x.dimensions(), y.dimensions());
+                for (p, q) in x.iter().zip(y.iter()) {
+                    assert!((*p - q).normal() < 1e-9);
+                }
+            };
+            close(&u.dot(&sigma).unwrap().dot(&vh).unwrap(), &a);
+            close(&adjoint(&u).dot(&u).unwrap(), &DataMatrix::identity(m));
+            close(&vh.dot(&adjoint(&vh)).unwrap(), &DataMatrix::identity(n));
+
+            // the squares of the singular values are the eigen values of `A^H A`, whose trace
+            // is the squared Frobenius norm
+            let s: Vec<f64> = (0..m.min(n)).map(|i| sigma.get(i, i).unwrap().0).collect();
+            assert!(s[0] >= s[1] && s[1] >= 0.0);
+            let frob: f64 = a.iter().map(|x| x.normal2()).sum();
+            assert!((s.iter().map(|x| x * x).sum::<f64>() - frob).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_svd_not_converged() {
+        let a = mat_![
+            1.0 2.0;
+            3.0 4.0;
+        ];
+        assert!(matches!(unsafe { svd_tall(&a, 0) }, Err(MatError::NotConverged { iterations: 0 })));
+        assert!(unsafe { svd_tall(&a, 75) }.is_ok());
+    }
+}
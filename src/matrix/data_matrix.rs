@@ -1,9 +1,30 @@
-use super::Mat;
-use crate::element::{LinearElem};
+use super::{IterMut, Mat};
+use crate::element::LinearElem;
 use crate::error::MatError;
 use MatError::*;
 
+/// Rows and cols of each tile of `rhs` packed in [`DataMatrix::dot_unchecked`], so that a tile
+/// of `f64` (or [`Complex`](crate::Complex)) fits in the L2 cache
+const DOT_TILE_ROWS: usize = 64;
+const DOT_TILE_COLS: usize = 256;
+/// Rows of each block handed to a thread by [`for_each_row_block`]
+const PAR_BLOCK_ROWS: usize = 16;
+
+#[cfg(feature = "parallel")]
+mod parallel;
+#[cfg(feature = "parallel")]
+pub(crate) use parallel::par_try_for_each;
+#[cfg(feature = "parallel")]
+pub use parallel::set_parallel_threshold;
+
 /// The fundamental matrix that holds the data
+///
+/// Arithmetic operators are implemented on [`DataMatrix`], references to it and `&dyn Mat`.
+/// Unlike [`Mat::add`] and [`Mat::sub`], which repeat or truncate `rhs`, the operators are
+/// strict: `+` and `-` need the same dimensions, and `*` (the matrix product) needs
+/// `lhs.cols() == rhs.rows()`. A mismatch panics with [`MatError::InconsistentDimension`]; use
+/// [`Mat::try_add`], [`Mat::try_sub`] and [`Mat::dot`] to get the error instead. A scalar on
+/// the right of `*` scales every element
 pub struct DataMatrix<T> {
     data: Vec<T>,
     cols: usize,
@@ -46,12 +67,19 @@ where
     }
 
     /// Explicitly convert a [`DataMatrix`]'s data to another type
+    ///
+    /// A transposed matrix stays transposed, so every element keeps its place
     pub fn convert<U: From<T>>(self) -> DataMatrix<U> {
+        self.convert_with(U::from)
+    }
+
+    /// Convert a [`DataMatrix`]'s data to another type by `f`, for conversions without [`From`]
+    pub fn convert_with<U, F: FnMut(T) -> U>(self, f: F) -> DataMatrix<U> {
         DataMatrix {
-            data: self.data.into_iter().map(|x| x.into()).collect(),
+            data: self.data.into_iter().map(f).collect(),
             rows: self.rows,
             cols: self.cols,
-            is_transposed: false,
+            is_transposed: self.is_transposed,
         }
     }
 
@@ -85,6 +113,141 @@ where
     
     /// Move data out of the matrix
     pub fn data(self) -> Vec<T> { self.data }
+
+    /// Iterate over mutable references to the elements row by row, taken transpose into
+    /// consideration
+    ///
+    /// Only [`DataMatrix`] has this, as other matrices don't own their elements in one buffer;
+    /// use [`Mat::map_inplace`] for them
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (rows, cols) = (self.rows(), self.cols());
+        let (row_stride, col_stride) = if self.is_transposed { (1, self.cols) } else { (self.cols, 1) };
+        unsafe { IterMut::new(&mut self.data, rows, cols, row_stride, col_stride) }
+    }
+
+    /// Split the matrix into disjoint blocks of `rows_per_block` consecutive rows (the last one
+    /// may be shorter), which can be mutated from different threads
+    ///
+    /// A transposed matrix is rearranged in memory first, so that its rows are contiguous. An
+    /// empty matrix has no blocks
+    ///
+    /// Panics if `rows_per_block` is zero
+    pub fn row_blocks_mut(&mut self, rows_per_block: usize) -> Vec<RowBlockMut<'_, T>> {
+        assert!(rows_per_block != 0, "rows_per_block must be positive");
+        self.untranspose();
+        if self.data.is_empty() {
+            return Vec::new();
+        }
+
+        let cols = self.cols;
+        self.data
+            .chunks_mut(rows_per_block * cols)
+            .enumerate()
+            .map(|(b, data)| RowBlockMut {
+                rows: data.len() / cols,
+                data,
+                first_row: b * rows_per_block,
+                cols,
+            })
+            .collect()
+    }
+
+    /// Rearrange the data of a transposed matrix, so that it's no longer transposed
+    fn untranspose(&mut self) {
+        if !self.is_transposed {
+            return;
+        }
+        let (rows, cols) = (self.rows(), self.cols());
+        let mut data = Vec::with_capacity(self.data.len());
+        for i in 0..rows {
+            for j in 0..cols {
+                data.push(self.data[j * self.cols + i].clone());
+            }
+        }
+        *self = DataMatrix {
+            data,
+            rows,
+            cols,
+            is_transposed: false,
+        };
+    }
+}
+
+/// Consecutive rows of a [`DataMatrix`], borrowed mutably from [`DataMatrix::row_blocks_mut`]
+///
+/// Unlike [`SliceMatrix`](crate::SliceMatrix), it's a plain slice of the data, so it's
+/// [`Send`] whenever `T` is
+pub struct RowBlockMut<'a, T> {
+    data: &'a mut [T],
+    first_row: usize,
+    rows: usize,
+    cols: usize,
+}
+
+impl<'a, T> RowBlockMut<'a, T> {
+    /// Index in the matrix of the first row in the block
+    pub fn first_row(&self) -> usize {
+        self.first_row
+    }
+    /// Number of rows in the block
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+    /// Number of cols, same as the matrix
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+    /// Row `i` of the block, that is row `first_row() + i` of the matrix
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+    /// Mutable row `i` of the block
+    pub fn row_mut(&mut self, i: usize) -> &mut [T] {
+        &mut self.data[i * self.cols..(i + 1) * self.cols]
+    }
+    /// Iterate over the rows, along with their indices in the matrix
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = (usize, &mut [T])> {
+        let first_row = self.first_row;
+        self.data.chunks_exact_mut(self.cols).enumerate().map(move |(i, row)| (i + first_row, row))
+    }
+}
+
+/// Run `f` on each row block of `m`, stopping at the first error
+///
+/// With the `parallel` feature, the blocks are visited across threads if `m` is large enough,
+/// by [`LinearElem::par_try_for_each_row_block`]. Otherwise they are visited in order
+///
+/// # Safety
+///
+/// `f` must only capture elements, containers of them and plain data, so that it can be shared
+/// across threads whenever `T` can
+pub(crate) unsafe fn try_for_each_row_block<T, F>(m: &mut DataMatrix<T>, f: F) -> Result<(), MatError>
+where
+    T: LinearElem,
+    F: Fn(RowBlockMut<'_, T>) -> Result<(), MatError>,
+{
+    #[cfg(feature = "parallel")]
+    if m.data.len() >= parallel::parallel_threshold() {
+        return T::par_try_for_each_row_block(m.row_blocks_mut(PAR_BLOCK_ROWS), f);
+    }
+    m.row_blocks_mut(PAR_BLOCK_ROWS).into_iter().try_for_each(f)
+}
+
+/// Same as [`try_for_each_row_block`] for an `f` that can't fail
+///
+/// # Safety
+///
+/// Same as [`try_for_each_row_block`]
+pub(crate) unsafe fn for_each_row_block<T, F>(m: &mut DataMatrix<T>, f: F)
+where
+    T: LinearElem,
+    F: Fn(RowBlockMut<'_, T>),
+{
+    try_for_each_row_block(m, |block| {
+        f(block);
+        Ok(())
+    })
+    .expect("the closure never fails")
 }
 
 impl<T> Mat for DataMatrix<T>
@@ -134,25 +297,64 @@ where
     unsafe fn get_mut_unchecked_raw(&mut self, i: usize, j: usize) -> &mut T {
         self.data.get_unchecked_mut(i * self.cols + j)
     }
-}
 
-impl<T: LinearElem> PartialEq for DataMatrix<T> {
-    fn eq(&self, other: &Self) -> bool {
-        if self.rows() != other.rows() || self.cols() != other.cols() {
-            return false;
-        }
+    /// Works on the raw data, tiled for cache
+    ///
+    /// Each tile of `rhs` is copied into a contiguous panel, then each row of the result is
+    /// accumulated from the panels, so that the inner loop runs over contiguous slices; for
+    /// `f64` and [`Complex`](crate::Complex) it's plain float arithmetic. Elements of `rhs` are
+    /// read only once, so `rhs` can be any [`Mat`] without losing much
+    ///
+    /// With the `parallel` feature, blocks of rows of the result are computed across threads
+    unsafe fn dot_unchecked(&self, rhs: &dyn Mat<Item = T>) -> DataMatrix<T> {
+        let (m, k, n) = (self.rows(), self.cols(), rhs.cols());
+        let mut result = DataMatrix::zeros(m, n);
+        // strides of `self` in the raw data
+        let (row_stride, col_stride) = if self.is_transposed { (1, self.cols) } else { (self.cols, 1) };
 
-        for i in 0..self.rows() {
-            for j in 0..self.cols() {
-                unsafe {
-                    if !self.get_unchecked(i, j).eq(other.get_unchecked(i, j)) {
-                        return false;
+        // `(jc, pc, panel)` where the panel is `rhs[pc..pc + kc, jc..jc + nc]`
+        let mut panels = Vec::new();
+        for jc in (0..n).step_by(DOT_TILE_COLS) {
+            let nc = DOT_TILE_COLS.min(n - jc);
+            for pc in (0..k).step_by(DOT_TILE_ROWS) {
+                let kc = DOT_TILE_ROWS.min(k - pc);
+                let mut panel = Vec::with_capacity(kc * nc);
+                for p in pc..pc + kc {
+                    for j in jc..jc + nc {
+                        panel.push(rhs.get_unchecked(p, j).clone());
                     }
                 }
+                panels.push((jc, pc, panel));
             }
         }
 
-        return true;
+        let data = &self.data;
+        for_each_row_block(&mut result, |mut block| {
+            for (jc, pc, panel) in panels.iter() {
+                let nc = DOT_TILE_COLS.min(n - jc);
+                for (i, row) in block.rows_mut() {
+                    let row = &mut row[*jc..jc + nc];
+                    for (p, b_row) in panel.chunks_exact(nc).enumerate() {
+                        let a = data.get_unchecked(i * row_stride + (pc + p) * col_stride);
+                        for (c, b) in row.iter_mut().zip(b_row) {
+                            *c += &(a.clone() * b);
+                        }
+                    }
+                }
+            }
+        });
+
+        result
+    }
+
+    fn as_data_matrix_mut(&mut self) -> Option<&mut DataMatrix<T>> {
+        Some(self)
+    }
+}
+
+impl<T: LinearElem> PartialEq for DataMatrix<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.dimensions() == other.dimensions() && self.iter().eq(other.iter())
     }
 }
 impl<T: LinearElem> Eq for DataMatrix<T> {}
@@ -0,0 +1,45 @@
+use super::RowBlockMut;
+use crate::error::MatError;
+use rayon::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+/// Matrices with fewer elements are not worth splitting across threads
+static PAR_MIN_LEN: AtomicUsize = AtomicUsize::new(1 << 14);
+
+/// Set the number of elements from which a matrix is split across threads, `1 << 14` by default
+pub fn set_parallel_threshold(len: usize) {
+    PAR_MIN_LEN.store(len, Relaxed);
+}
+
+pub(super) fn parallel_threshold() -> usize {
+    PAR_MIN_LEN.load(Relaxed)
+}
+
+/// A closure shared across threads, see [`LinearElem::par_try_for_each_row_block`](crate::element::LinearElem::par_try_for_each_row_block)
+struct SharedFn<F>(F);
+unsafe impl<F> Send for SharedFn<F> {}
+unsafe impl<F> Sync for SharedFn<F> {}
+
+impl<F> SharedFn<F> {
+    fn call<T>(&self, block: RowBlockMut<'_, T>) -> Result<(), MatError>
+    where
+        F: Fn(RowBlockMut<'_, T>) -> Result<(), MatError>,
+    {
+        (self.0)(block)
+    }
+}
+
+/// Run `f` on `blocks` across threads, stopping at the first error
+///
+/// # Safety
+///
+/// `f` must only capture elements of type `T`, containers of them and plain data, so that it
+/// can be shared across threads as `T` can
+pub(crate) unsafe fn par_try_for_each<T, F>(blocks: Vec<RowBlockMut<'_, T>>, f: F) -> Result<(), MatError>
+where
+    T: Send + Sync,
+    F: Fn(RowBlockMut<'_, T>) -> Result<(), MatError>,
+{
+    let f = SharedFn(f);
+    blocks.into_par_iter().try_for_each(|block| f.call(block))
+}
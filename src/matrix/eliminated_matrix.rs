@@ -1,10 +1,69 @@
+use super::try_for_each_row_block;
 use super::DataMatrix;
 use super::Mat;
 use super::SliceMatrix;
 use crate::element::*;
+use crate::error::MatError;
+
+/// `a + b`, or [`MatError::Overflow`]
+pub(crate) fn checked_add<T: LinearElem>(a: &T, b: &T) -> Result<T, MatError> {
+    a.checked_add(b).ok_or(MatError::Overflow)
+}
+
+/// `a * b`, or [`MatError::Overflow`]
+pub(crate) fn checked_mul<T: LinearElem>(a: &T, b: &T) -> Result<T, MatError> {
+    a.checked_mul(b).ok_or(MatError::Overflow)
+}
+
+/// `a - b`, or [`MatError::Overflow`]
+pub(crate) fn checked_sub<T: LinearElem>(a: &T, b: &T) -> Result<T, MatError> {
+    a.checked_sub(b).ok_or(MatError::Overflow)
+}
+
+/// Subtract `factor` times row `src` from row `dst`, reporting overflow
+unsafe fn row_sub_scaled<T, M>(mat: &mut M, dst: usize, src: usize, factor: &T) -> Result<(), MatError>
+where
+    T: LinearElem,
+    M: Mat<Item = T> + ?Sized,
+{
+    for j in 0..mat.cols() {
+        let val = checked_sub(
+            mat.get_unchecked(dst, j),
+            &checked_mul(mat.get_unchecked(src, j), factor)?,
+        )?;
+        *mat.get_mut_unchecked(dst, j) = val;
+    }
+    Ok(())
+}
+
+/// Eliminate the elements below `(pivot_row, pivot_col)` of a [`DataMatrix`] on its raw data,
+/// updating blocks of rows across threads with the `parallel` feature
+unsafe fn eliminate_below<T>(mat: &mut DataMatrix<T>, pivot_row: usize, pivot_col: usize) -> Result<(), MatError>
+where
+    T: LinearElem + Inv,
+{
+    let pivot: Vec<T> = (0..mat.cols()).map(|j| mat.get_unchecked(pivot_row, j).clone()).collect();
+    let inv = pivot[pivot_col].clone().inv();
+
+    try_for_each_row_block(mat, |mut block| {
+        for (i, row) in block.rows_mut() {
+            if i <= pivot_row || row[pivot_col].is_add_zero() {
+                continue;
+            }
+            let factor = checked_mul(&row[pivot_col], &inv)?;
+            for (x, p) in row.iter_mut().zip(pivot.iter()) {
+                *x = checked_sub(x, &checked_mul(p, &factor)?)?;
+            }
+        }
+        Ok(())
+    })
+}
 
 /// Implementation of Gussian Elimination
-pub unsafe fn elimination<T, M: Mat<Item = T>>(mat: &mut M) -> [Vec<Option<usize>>; 2]
+///
+/// Returns [`MatError::Overflow`] if some intermediate result can't be represented by `T`, in which
+/// case `mat` is left partially eliminated
+pub unsafe fn elimination<T, M: Mat<Item = T>>(mat: &mut M) -> Result<[Vec<Option<usize>>; 2], MatError>
 where
     T: LinearElem + Inv,
 {
@@ -30,18 +89,21 @@ where
 
         // eliminate the zeros in following rows
         if found_non_zero {
-            for i in pivot_row + 1..mat.rows() {
-                // if the row is already zero
-                if mat.get_unchecked(i, pivot_col).is_add_zero() {
-                    continue;
-                }
+            if let Some(m) = mat.as_data_matrix_mut() {
+                eliminate_below(m, pivot_row, pivot_col)?;
+            } else {
+                for i in pivot_row + 1..mat.rows() {
+                    // if the row is already zero
+                    if mat.get_unchecked(i, pivot_col).is_add_zero() {
+                        continue;
+                    }
 
-                mat.row_unchecked(i).sub_assign_unchecked(
-                    mat.row_unchecked(pivot_row).clone_data().scale(
-                        &mat.get_unchecked(i, pivot_col).clone()
-                            .mul(&mat.get_unchecked(pivot_row, pivot_col).clone().inv()),
-                    ),
-                );
+                    let factor = checked_mul(
+                        mat.get_unchecked(i, pivot_col),
+                        &mat.get_unchecked(pivot_row, pivot_col).clone().inv(),
+                    )?;
+                    row_sub_scaled(mat, i, pivot_row, &factor)?;
+                }
             }
             *pivot_cols.get_unchecked_mut(pivot_row) = Some(pivot_col);
             *pivot_rows.get_unchecked_mut(pivot_col) = Some(pivot_row);
@@ -52,7 +114,7 @@ where
         pivot_col += 1;
     }
 
-    [pivot_rows, pivot_cols]
+    Ok([pivot_rows, pivot_cols])
 }
 
 /// An eliminated matrix holidng information of where pivots are
@@ -76,37 +138,43 @@ where
     T: LinearElem + Inv,
 {
     /// Eliminate a matrix and stores it in a [`EliminatedMatrix`]
-    pub fn eliminated(mut mat: M) -> Self {
-        let [pivot_rows, pivot_cols] = unsafe { elimination(&mut mat) };
-        Self {
+    ///
+    /// Fails with [`MatError::Overflow`] if the elements overflow during elimination
+    pub fn eliminated(mut mat: M) -> Result<Self, MatError> {
+        let [pivot_rows, pivot_cols] = unsafe { elimination(&mut mat)? };
+        Ok(Self {
             mat,
             pivot_cols,
             pivot_rows,
-        }
+        })
     }
 
     /// Scale each row so that pivots are `1`s
-    pub fn simplify(&mut self) {
+    pub fn simplify(&mut self) -> Result<(), MatError> {
         unsafe {
             for i in 0..self.rows() {
                 match self.pivot_cols.get_unchecked(i) {
                     None => break,
                     Some(pivot_col) => {
-                        self.row_unchecked(i)
-                            .scale(&self.get_unchecked(i, *pivot_col).clone().inv());
+                        let s = self.get_unchecked(i, *pivot_col).clone().inv();
+                        for j in 0..self.cols() {
+                            let val = checked_mul(self.get_unchecked(i, j), &s)?;
+                            *self.get_mut_unchecked(i, j) = val;
+                        }
                     }
                 }
             }
         }
+        Ok(())
     }
-    pub fn simplified(mut self) -> Self {
-        self.simplify();
-        self
+    pub fn simplified(mut self) -> Result<Self, MatError> {
+        self.simplify()?;
+        Ok(self)
     }
 
     /// Using row transformations to make all elements above a pivot zeros
-    pub fn reduce(&mut self) {
-        self.simplify();
+    pub fn reduce(&mut self) -> Result<(), MatError> {
+        self.simplify()?;
 
         unsafe {
             for i in 0..self.rows() {
@@ -122,11 +190,8 @@ where
                                 None => continue,
                                 Some(pivot_row) => {
                                     // subtract the found pivot from current row
-                                    self.row_unchecked(i).sub_assign_unchecked(
-                                        self.row_unchecked(*pivot_row)
-                                            .clone_data()
-                                            .scale(self.get_unchecked(i, j)),
-                                    );
+                                    let factor = self.get_unchecked(i, j).clone();
+                                    row_sub_scaled(self, i, *pivot_row, &factor)?;
                                 }
                             }
                         }
@@ -134,10 +199,11 @@ where
                 }
             }
         }
+        Ok(())
     }
-    pub fn reduced(mut self) -> Self {
-        self.reduce();
-        self
+    pub fn reduced(mut self) -> Result<Self, MatError> {
+        self.reduce()?;
+        Ok(self)
     }
 
     /// Count the rank of the matrix
@@ -153,12 +219,23 @@ where
         r
     }
 
+    /// `self[row, col_begin..] * sol[col_begin.., 0]`, reporting overflow
+    unsafe fn row_dot_checked(&self, row: usize, col_begin: usize, sol: &DataMatrix<T>) -> Result<T, MatError> {
+        let mut acc = T::add_zero();
+        for j in col_begin..self.cols() {
+            acc = acc
+                .checked_add(&checked_mul(self.get_unchecked(row, j), sol.get_unchecked(j, 0))?)
+                .ok_or(MatError::Overflow)?;
+        }
+        Ok(acc)
+    }
+
     /// Return a [`DataMatrix`] cols of which holds the basis for the null space, if null space is
     /// not {0};
     /// Otherwise returns [`None`]
-    pub fn null_space(&self) -> Option<DataMatrix<T>> {
+    pub fn null_space(&self) -> Result<Option<DataMatrix<T>>, MatError> {
         if self.rank() == self.cols() {
-            return None;
+            return Ok(None);
         };
 
         let result = DataMatrix::zeros(self.cols(), self.cols() - self.rank());
@@ -176,34 +253,25 @@ where
                 *sol.get_mut_unchecked(*free, 0) = T::mul_zero();
 
                 for pivot in pivot_vars.iter().rev() {
-                    let sol_slice =
-                        SliceMatrix::new_unchecked(&sol, pivot + 1, self.cols() - pivot - 1, 0, 1);
-                    let coef_slice = SliceMatrix::new_unchecked(
-                        self,
-                        self.pivot_rows.get_unchecked(*pivot).unwrap(),
-                        1,
-                        pivot + 1,
-                        self.cols() - pivot - 1,
-                    );
-
-                    let neg_pivot_val = coef_slice.dot_unchecked(&sol_slice);
-                    let neg_pivot_val = neg_pivot_val.get_unchecked(0, 0);
-                    *sol.get_mut_unchecked(*pivot, 0) = T::add_zero()
-                        .sub(neg_pivot_val)
-                        .mul(&self.get_unchecked(self.pivot_rows.get_unchecked(*pivot).unwrap(), *pivot).clone().inv());
+                    let pivot_row = self.pivot_rows.get_unchecked(*pivot).unwrap();
+                    let neg_pivot_val = self.row_dot_checked(pivot_row, pivot + 1, &sol)?;
+                    *sol.get_mut_unchecked(*pivot, 0) = checked_mul(
+                        &checked_sub(&T::add_zero(), &neg_pivot_val)?,
+                        &self.get_unchecked(pivot_row, *pivot).clone().inv(),
+                    )?;
                 }
 
                 result.col_unchecked(i).add_assign_unchecked(&sol);
             }
         }
 
-        Some(result)
+        Ok(Some(result))
     }
 
     /// Calculate the special solution of `self*x=b`, where all free variables are set to `0`
     ///
     /// If their is no solution, returns [`None`]
-    pub fn special_solution(&self, b: &dyn Mat<Item = T>) -> Option<DataMatrix<T>> {
+    pub fn special_solution(&self, b: &dyn Mat<Item = T>) -> Result<Option<DataMatrix<T>>, MatError> {
         let mut sol = DataMatrix::zeros(self.cols(), 1);
 
         unsafe {
@@ -211,26 +279,21 @@ where
                 match self.pivot_cols.get_unchecked(i) {
                     None => {
                         if !b.get_unchecked(i, 0).is_add_zero() {
-                            return None;
+                            return Ok(None);
                         }
                     }
                     Some(pivot) => {
-                        let sol_slice =
-                            SliceMatrix::new_unchecked(&sol, pivot + 1, self.cols() - pivot - 1, 0, 1);
-                        let coef_slice =
-                            SliceMatrix::new_unchecked(self, i, 1, pivot + 1, self.cols() - pivot - 1);
-
-                        let tmp = coef_slice.dot_unchecked(&sol_slice);
-                        let tmp = tmp.get_unchecked(0, 0);
-                        *sol.get_mut_unchecked(*pivot, 0) = b.get_unchecked(i, 0).clone()
-                            .sub(tmp)
-                            .mul(&self.get_unchecked(i, *pivot).clone().inv());
+                        let tmp = self.row_dot_checked(i, pivot + 1, &sol)?;
+                        *sol.get_mut_unchecked(*pivot, 0) = checked_mul(
+                            &checked_sub(b.get_unchecked(i, 0), &tmp)?,
+                            &self.get_unchecked(i, *pivot).clone().inv(),
+                        )?;
                     }
                 }
             }
         }
 
-        Some(sol)
+        Ok(Some(sol))
     }
 
     /// Returns a slice of `self`, retaining the pivot information
@@ -337,7 +400,7 @@ mod display {
 mod test {
 
     use super::super::{DataMatrix, EliminatedMatrix, Mat};
-    use crate::Rational;
+    use crate::{rational, Rational};
     use mat_macro::mat_;
 
     fn eliminate() -> EliminatedMatrix<Rational, DataMatrix<Rational>> {
@@ -348,12 +411,12 @@ mod test {
         ];
         let b: DataMatrix<Rational> = a.convert();
 
-        let b = EliminatedMatrix::eliminated(b);
+        let b = EliminatedMatrix::eliminated(b).unwrap();
 
         assert_eq!(&b as &dyn Mat<Item=_>, &mat_![
-            (Rational(2, 1))  (Rational(1, 1))  (Rational(-1, 1))  (Rational(1, 1)) (Rational(1, 1));
-            (Rational(0, 1))  (Rational(-7, 2)) (Rational(5, 2))  (Rational(-9, 2))  (Rational(5, 2));
-            (Rational(0, 1))  (Rational(0, 1))   (Rational(0, 1))  (Rational(0, 1)) (Rational(0, 1));
+            (rational!(2, 1))  (rational!(1, 1))  (rational!(-1, 1))  (rational!(1, 1)) (rational!(1, 1));
+            (rational!(0, 1))  (rational!(-7, 2)) (rational!(5, 2))  (rational!(-9, 2))  (rational!(5, 2));
+            (rational!(0, 1))  (rational!(0, 1))   (rational!(0, 1))  (rational!(0, 1)) (rational!(0, 1));
         ].convert() as &dyn Mat<Item=_>);
 
         b
@@ -375,14 +438,14 @@ mod test {
         ]
         .convert();
 
-        let b = a.transposed().eliminated();
+        let b = a.transposed().eliminated().unwrap();
 
         assert_eq!(
             &b as &dyn Mat<Item = _>,
             &mat_![
-                (Rational(2, 1))  (Rational(1, 1))  (Rational(-1, 1))  (Rational(1, 1)) (Rational(1, 1));
-                (Rational(0, 1))  (Rational(-7, 2)) (Rational(5, 2))  (Rational(-9, 2))  (Rational(5, 2));
-                (Rational(0, 1))  (Rational(0, 1))   (Rational(0, 1))  (Rational(0, 1)) (Rational(0, 1));
+                (rational!(2, 1))  (rational!(1, 1))  (rational!(-1, 1))  (rational!(1, 1)) (rational!(1, 1));
+                (rational!(0, 1))  (rational!(-7, 2)) (rational!(5, 2))  (rational!(-9, 2))  (rational!(5, 2));
+                (rational!(0, 1))  (rational!(0, 1))   (rational!(0, 1))  (rational!(0, 1)) (rational!(0, 1));
             ] as &dyn Mat<Item = _>
         );
     }
@@ -390,24 +453,24 @@ mod test {
     #[test]
     fn test_simplify() {
         let mut b = eliminate();
-        b.simplify();
+        b.simplify().unwrap();
 
         assert_eq!(&b as &dyn Mat<Item=Rational>, &mat_![
-            (Rational(1, 1))  (Rational(1, 2))  (Rational(-1, 2))  (Rational(1, 2)) (Rational(1, 2));
-            (Rational(0, 1))  (Rational(1, 1)) (Rational(-5, 7))  (Rational(9, 7))  (Rational(-5, 7));
-            (Rational(0, 1))  (Rational(0, 1))   (Rational(0, 1))  (Rational(0, 1)) (Rational(0, 1));
+            (rational!(1, 1))  (rational!(1, 2))  (rational!(-1, 2))  (rational!(1, 2)) (rational!(1, 2));
+            (rational!(0, 1))  (rational!(1, 1)) (rational!(-5, 7))  (rational!(9, 7))  (rational!(-5, 7));
+            (rational!(0, 1))  (rational!(0, 1))   (rational!(0, 1))  (rational!(0, 1)) (rational!(0, 1));
         ].convert() as &dyn Mat<Item=Rational>);
     }
 
     #[test]
     fn test_reduced() {
         let mut b = eliminate();
-        b.reduce();
+        b.reduce().unwrap();
 
         assert_eq!(&b as &dyn Mat<Item=Rational>, &mat_![
-            (Rational(1, 1))  (Rational(0, 1))  (Rational(-1, 7))  (Rational(-1, 7)) (Rational(6, 7));
-            (Rational(0, 1))  (Rational(1, 1)) (Rational(-5, 7))  (Rational(9, 7))  (Rational(-5, 7));
-            (Rational(0, 1))  (Rational(0, 1))   (Rational(0, 1))  (Rational(0, 1)) (Rational(0, 1));
+            (rational!(1, 1))  (rational!(0, 1))  (rational!(-1, 7))  (rational!(-1, 7)) (rational!(6, 7));
+            (rational!(0, 1))  (rational!(1, 1)) (rational!(-5, 7))  (rational!(9, 7))  (rational!(-5, 7));
+            (rational!(0, 1))  (rational!(0, 1))   (rational!(0, 1))  (rational!(0, 1)) (rational!(0, 1));
         ].convert() as &dyn Mat<Item=Rational>);
     }
 
@@ -420,7 +483,7 @@ mod test {
     #[test]
     fn test_rank2() {
         let m = mat_![1 2; 3 4;];
-        let m = m.eliminated();
+        let m = m.eliminated().unwrap();
         assert_eq!(m.rank(), 2)
     }
 
@@ -431,8 +494,8 @@ mod test {
             0 2 6 2;
             0 0 0 0;
         ].convert();
-        let b = a.eliminated();
-        let n = b.null_space().unwrap();
+        let b = a.eliminated().unwrap();
+        let n = b.null_space().unwrap().unwrap();
 
         assert_eq!(
             n,
@@ -452,14 +515,14 @@ mod test {
             0 1 3 1;
             0 0 0 0;
         ].convert();
-        let a = a.eliminated();
+        let a = a.eliminated().unwrap();
         let b: DataMatrix<Rational> = mat_![
             1;
             2;
             0;
         ].convert();
 
-        let special_solution = a.special_solution(&b).unwrap();
+        let special_solution = a.special_solution(&b).unwrap().unwrap();
         assert_eq!(
             special_solution,
             mat_![
@@ -470,4 +533,23 @@ mod test {
             ].convert()
         );
     }
+
+    #[test]
+    #[cfg(not(feature = "big_rational"))]
+    fn test_elimination_overflow() {
+        use crate::error::MatError;
+        use crate::rational::Integer;
+
+        let a: DataMatrix<Rational> = mat_![
+            (rational!(1, 1)) (rational!(Integer::MAX, 1));
+            (rational!(-1, 1)) (rational!(1, 1));
+        ];
+        assert!(matches!(a.eliminated(), Err(MatError::Overflow)));
+
+        let a: DataMatrix<i32> = mat_![
+            1 (i32::MAX);
+            (-1) 1;
+        ];
+        assert!(matches!(a.eliminated(), Err(MatError::Overflow)));
+    }
 }
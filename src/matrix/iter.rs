@@ -0,0 +1,297 @@
+use std::marker::PhantomData;
+
+use super::{DataMatrix, Mat, SliceMatrix};
+use crate::element::LinearElem;
+
+/// Iterator over the elements of a [`Mat`] row by row, from [`Mat::iter`]
+pub struct Iter<'a, T> {
+    mat: &'a dyn Mat<Item = T>,
+    index: usize,
+    len: usize,
+}
+
+impl<'a, T: LinearElem> Iter<'a, T> {
+    /// Same as [`Mat::iter`], which also works for trait objects
+    pub fn new(mat: &'a dyn Mat<Item = T>) -> Self {
+        Iter {
+            mat,
+            index: 0,
+            len: mat.rows() * mat.cols(),
+        }
+    }
+}
+
+impl<'a, T: LinearElem> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<&'a T> {
+        if self.index == self.len {
+            return None;
+        }
+        let cols = self.mat.cols();
+        let (i, j) = (self.index / cols, self.index % cols);
+        self.index += 1;
+        unsafe { Some(self.mat.get_unchecked(i, j)) }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len - self.index, Some(self.len - self.index))
+    }
+}
+impl<'a, T: LinearElem> ExactSizeIterator for Iter<'a, T> {}
+
+/// Same as [`Iter`], but yields `((i, j), element)`, from [`Mat::indexed_iter`]
+pub struct IndexedIter<'a, T>(Iter<'a, T>);
+
+impl<'a, T: LinearElem> IndexedIter<'a, T> {
+    /// Same as [`Mat::indexed_iter`], which also works for trait objects
+    pub fn new(mat: &'a dyn Mat<Item = T>) -> Self {
+        IndexedIter(Iter::new(mat))
+    }
+}
+
+impl<'a, T: LinearElem> Iterator for IndexedIter<'a, T> {
+    type Item = ((usize, usize), &'a T);
+    fn next(&mut self) -> Option<Self::Item> {
+        let cols = self.0.mat.cols();
+        let index = self.0.index;
+        self.0.next().map(|x| ((index / cols, index % cols), x))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+impl<'a, T: LinearElem> ExactSizeIterator for IndexedIter<'a, T> {}
+
+/// Iterator over mutable references to the elements of a [`DataMatrix`] row by row, from
+/// [`DataMatrix::iter_mut`]
+pub struct IterMut<'a, T> {
+    data: *mut T,
+    cols: usize,
+    // strides of the rows and cols in the raw data
+    row_stride: usize,
+    col_stride: usize,
+    index: usize,
+    len: usize,
+    _phantom: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> IterMut<'a, T> {
+    /// Elements of a `rows x cols` matrix, where element `(i, j)` is
+    /// `data[i * row_stride + j * col_stride]`, and these indices are distinct and in bounds
+    pub(crate) unsafe fn new(data: &'a mut [T], rows: usize, cols: usize, row_stride: usize, col_stride: usize) -> Self {
+        IterMut {
+            data: data.as_mut_ptr(),
+            cols,
+            row_stride,
+            col_stride,
+            index: 0,
+            len: rows * cols,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<&'a mut T> {
+        if self.index == self.len {
+            return None;
+        }
+        let (i, j) = (self.index / self.cols, self.index % self.cols);
+        self.index += 1;
+        // each element is handed out once, as the indices are distinct
+        unsafe { Some(&mut *self.data.add(i * self.row_stride + j * self.col_stride)) }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len - self.index, Some(self.len - self.index))
+    }
+}
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+/// Iterator over the rows of a [`Mat`] as [`SliceMatrix`], from [`Mat::rows_iter`]
+pub struct RowsIter<'a, T> {
+    mat: &'a dyn Mat<Item = T>,
+    begin: usize,
+    end: usize,
+}
+
+impl<'a, T: LinearElem> RowsIter<'a, T> {
+    /// Same as [`Mat::rows_iter`], which also works for trait objects
+    pub fn new(mat: &'a dyn Mat<Item = T>) -> Self {
+        RowsIter {
+            mat,
+            begin: 0,
+            end: mat.rows(),
+        }
+    }
+}
+
+impl<'a, T: LinearElem> Iterator for RowsIter<'a, T> {
+    type Item = SliceMatrix<'a, T>;
+    fn next(&mut self) -> Option<SliceMatrix<'a, T>> {
+        if self.begin == self.end {
+            return None;
+        }
+        self.begin += 1;
+        unsafe { Some(SliceMatrix::new_unchecked(self.mat, self.begin - 1, 1, 0, self.mat.cols())) }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.end - self.begin, Some(self.end - self.begin))
+    }
+}
+impl<'a, T: LinearElem> DoubleEndedIterator for RowsIter<'a, T> {
+    fn next_back(&mut self) -> Option<SliceMatrix<'a, T>> {
+        if self.begin == self.end {
+            return None;
+        }
+        self.end -= 1;
+        unsafe { Some(SliceMatrix::new_unchecked(self.mat, self.end, 1, 0, self.mat.cols())) }
+    }
+}
+impl<'a, T: LinearElem> ExactSizeIterator for RowsIter<'a, T> {}
+
+/// Iterator over the cols of a [`Mat`] as [`SliceMatrix`], from [`Mat::cols_iter`]
+pub struct ColsIter<'a, T> {
+    mat: &'a dyn Mat<Item = T>,
+    begin: usize,
+    end: usize,
+}
+
+impl<'a, T: LinearElem> ColsIter<'a, T> {
+    /// Same as [`Mat::cols_iter`], which also works for trait objects
+    pub fn new(mat: &'a dyn Mat<Item = T>) -> Self {
+        ColsIter {
+            mat,
+            begin: 0,
+            end: mat.cols(),
+        }
+    }
+}
+
+impl<'a, T: LinearElem> Iterator for ColsIter<'a, T> {
+    type Item = SliceMatrix<'a, T>;
+    fn next(&mut self) -> Option<SliceMatrix<'a, T>> {
+        if self.begin == self.end {
+            return None;
+        }
+        self.begin += 1;
+        unsafe { Some(SliceMatrix::new_unchecked(self.mat, 0, self.mat.rows(), self.begin - 1, 1)) }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.end - self.begin, Some(self.end - self.begin))
+    }
+}
+impl<'a, T: LinearElem> DoubleEndedIterator for ColsIter<'a, T> {
+    fn next_back(&mut self) -> Option<SliceMatrix<'a, T>> {
+        if self.begin == self.end {
+            return None;
+        }
+        self.end -= 1;
+        unsafe { Some(SliceMatrix::new_unchecked(self.mat, 0, self.mat.rows(), self.end, 1)) }
+    }
+}
+impl<'a, T: LinearElem> ExactSizeIterator for ColsIter<'a, T> {}
+
+/// Collect the elements into a column
+impl<T: LinearElem> FromIterator<T> for DataMatrix<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        DataMatrix::one_col(iter.into_iter().collect())
+    }
+}
+
+/// Collect the rows into a matrix
+///
+/// Panics if the rows are not of the same length
+impl<T: LinearElem> FromIterator<Vec<T>> for DataMatrix<T> {
+    fn from_iter<I: IntoIterator<Item = Vec<T>>>(iter: I) -> Self {
+        let mut data = Vec::new();
+        let (mut rows, mut cols) = (0, 0);
+        for row in iter {
+            if rows == 0 {
+                cols = row.len();
+            } else if row.len() != cols {
+                panic!("Row {} has {} elements, but row 0 has {}", rows, row.len(), cols);
+            }
+            data.extend(row);
+            rows += 1;
+        }
+        unsafe { DataMatrix::new_unchecked(data, rows, cols) }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::SparseMatrix;
+    use mat_macro::mat_;
+
+    #[test]
+    fn test_iter() {
+        let mut a: DataMatrix<i32> = mat_![1 2 3; 4 5 6;];
+        assert_eq!(a.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5, 6]);
+        a.transpose();
+        assert_eq!(a.iter().len(), 6);
+        assert_eq!(a.iter().copied().collect::<Vec<_>>(), vec![1, 4, 2, 5, 3, 6]);
+        assert_eq!(a.indexed_iter().nth(3), Some(((1, 1), &5)));
+
+        for x in a.iter_mut() {
+            *x *= 10;
+        }
+        assert_eq!(a, mat_![10 40; 20 50; 30 60;]);
+
+        let rows: Vec<i32> = a.rows_iter().map(|r| r.iter().sum()).collect();
+        assert_eq!(rows, vec![50, 70, 90]);
+        let cols: Vec<DataMatrix<i32>> = a.cols_iter().rev().map(|c| c.clone_data()).collect();
+        assert_eq!(cols, vec![mat_![40; 50; 60;], mat_![10; 20; 30;]]);
+
+        let empty: DataMatrix<i32> = DataMatrix::zeros(0, 3);
+        assert_eq!(empty.iter().count(), 0);
+        assert_eq!(empty.cols_iter().count(), 3);
+    }
+
+    #[test]
+    fn test_iter_dyn() {
+        let a: DataMatrix<i32> = mat_![1 2 3; 4 5 6;];
+        let s = SparseMatrix::from_dense(&a);
+        for m in [&a as &dyn Mat<Item = i32>, &s] {
+            assert_eq!(Iter::new(m).copied().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5, 6]);
+            assert_eq!(IndexedIter::new(m).nth(4), Some(((1, 1), &5)));
+            assert_eq!(RowsIter::new(m).map(|r| r.iter().sum()).collect::<Vec<i32>>(), vec![6, 15]);
+            assert_eq!(ColsIter::new(m).map(|c| c.iter().sum()).collect::<Vec<i32>>(), vec![5, 7, 9]);
+        }
+    }
+
+    #[test]
+    fn test_map_inplace_sparse() {
+        // only the stored elements, and those becoming zero are dropped
+        let mut s: SparseMatrix<i32> = SparseMatrix::from_triplets(2, 2, vec![(0, 1, 3), (1, 0, -1)]).unwrap();
+        s.map_inplace(|x| *x += 1);
+        assert_eq!(s.clone_data(), mat_![0 4; 0 0;]);
+        assert_eq!(s.nnz(), 1);
+    }
+
+    #[test]
+    fn test_map_dyn() {
+        let a: DataMatrix<i32> = mat_![1 2; 3 4;];
+        let mut s = SparseMatrix::from_dense(&a);
+        s.transpose();
+        let m: &dyn Mat<Item = i32> = &s;
+        assert_eq!(m.view().map(|x| x * 2), mat_![2 6; 4 8;]);
+        assert_eq!(m.view().hadamard(&a).unwrap(), mat_![1 6; 6 16;]);
+        assert_eq!(m.view().zip_with(&a, |x, y| x - y).unwrap(), mat_![0 1; (-1) 0;]);
+    }
+
+    #[test]
+    fn test_collect() {
+        let a: DataMatrix<i32> = mat_![1 2; 3 4;];
+        let b: DataMatrix<i32> = a.rows_iter().map(|r| r.iter().map(|x| x * 2).collect::<Vec<_>>()).collect();
+        assert_eq!(b, mat_![2 4; 6 8;]);
+        let c: DataMatrix<i32> = (1..4).collect();
+        assert_eq!(c, mat_![1; 2; 3;]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_collect_ragged() {
+        let _: DataMatrix<i32> = vec![vec![1, 2], vec![3]].into_iter().collect();
+    }
+}
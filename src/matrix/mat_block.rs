@@ -1,6 +1,6 @@
 use super::*;
 use crate::element::*;
-use std::ops::{Sub, Add, Mul, SubAssign, AddAssign, MulAssign};
+use std::ops::{Sub, Add, Mul, Neg, SubAssign, AddAssign, MulAssign};
 
 pub struct MatBlock<'a, T: LinearElem + 'a>(pub Box<dyn Mat<Item = T> + 'a>);
 
@@ -140,6 +140,46 @@ where
     }
 }
 
+/// The by-value forms of the operators above. Like them, `+` and `-` repeat or truncate `rhs`,
+/// since the zero block is `1x1`, while `*` panics on a mismatch
+macro_rules! impl_block_op {
+    ($trait: ident, $method: ident) => {
+        impl<'a, T> $trait<Self> for MatBlock<'a, T>
+        where
+            T: LinearElem + 'a,
+        {
+            type Output = Self;
+            fn $method(self, rhs: Self) -> Self::Output {
+                $trait::<&Self>::$method(self, &rhs)
+            }
+        }
+    };
+}
+impl_block_op!(Add, add);
+impl_block_op!(Sub, sub);
+impl_block_op!(Mul, mul);
+
+impl<'a, T> Neg for MatBlock<'a, T>
+where
+    T: LinearElem + 'a,
+{
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        MatBlock(Box::new(-self.0.clone_data()))
+    }
+}
+
+/// Scale every element of the block
+impl<'a, T> Mul<T> for MatBlock<'a, T>
+where
+    T: LinearElem + 'a,
+{
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output {
+        MatBlock(Box::new(self.0.clone_data() * rhs))
+    }
+}
+
 impl<'a, T> Inv for MatBlock<'a, T>
 where
     T: LinearElem + 'a + Inv,
@@ -207,4 +247,13 @@ mod test {
             ]
         );
     }
+
+    #[test]
+    fn test_block_ops() {
+        let a = mat_block!(mat_![1 2; 3 4;]);
+        let b = mat_block!(mat_![0 1; 1 0;]);
+        assert_eq!(a.clone() * b.clone(), mat_block!(mat_![2 1; 4 3;]));
+        assert_eq!(-(a.clone() - b) * 2, mat_block!(mat_![(-2) (-2); (-4) (-8);]));
+        assert_eq!(a + MatBlock::add_zero(), mat_block!(mat_![1 2; 3 4;]));
+    }
 }
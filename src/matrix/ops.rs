@@ -0,0 +1,180 @@
+use super::{DataMatrix, Mat};
+use crate::element::LinearElem;
+use crate::error::MatError;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Panic unless `a` and `b` have the same dimensions
+fn assert_same_dimensions<T: LinearElem>(a: &dyn Mat<Item = T>, b: &dyn Mat<Item = T>) {
+    if a.dimensions() != b.dimensions() {
+        panic!("{}", MatError::InconsistentDimension { need: a.dimensions(), got: b.dimensions() });
+    }
+}
+
+/// `a += b`, strictly
+fn add_to<T: LinearElem>(mut a: DataMatrix<T>, b: &dyn Mat<Item = T>) -> DataMatrix<T> {
+    assert_same_dimensions(&a, b);
+    unsafe { a.add_assign_unchecked(b) };
+    a
+}
+
+/// `a -= b`, strictly
+fn sub_from<T: LinearElem>(mut a: DataMatrix<T>, b: &dyn Mat<Item = T>) -> DataMatrix<T> {
+    assert_same_dimensions(&a, b);
+    unsafe { a.sub_assign_unchecked(b) };
+    a
+}
+
+/// `a * b`, panicking on a mismatch
+fn dot<T: LinearElem>(a: &dyn Mat<Item = T>, b: &dyn Mat<Item = T>) -> DataMatrix<T> {
+    a.dot(b).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// `-a`, element by element
+fn neg<T: LinearElem>(mut a: DataMatrix<T>) -> DataMatrix<T> {
+    for x in a.iter_mut() {
+        *x = T::add_zero() - &*x;
+    }
+    a
+}
+
+/// Implements `$trait` for owned and borrowed [`DataMatrix`], with a [`DataMatrix`] or a
+/// `&dyn Mat` on the right; `$owned` takes the left operand by value, `$borrowed` by reference
+macro_rules! impl_binary_op {
+    ($trait: ident, $method: ident, $owned: expr, $borrowed: expr) => {
+        impl<T: LinearElem> $trait<&DataMatrix<T>> for DataMatrix<T> {
+            type Output = DataMatrix<T>;
+            fn $method(self, rhs: &DataMatrix<T>) -> DataMatrix<T> {
+                $owned(self, rhs)
+            }
+        }
+        impl<T: LinearElem> $trait<DataMatrix<T>> for DataMatrix<T> {
+            type Output = DataMatrix<T>;
+            fn $method(self, rhs: DataMatrix<T>) -> DataMatrix<T> {
+                $owned(self, &rhs)
+            }
+        }
+        impl<T: LinearElem> $trait<&DataMatrix<T>> for &DataMatrix<T> {
+            type Output = DataMatrix<T>;
+            fn $method(self, rhs: &DataMatrix<T>) -> DataMatrix<T> {
+                $borrowed(self, rhs)
+            }
+        }
+        impl<T: LinearElem> $trait<DataMatrix<T>> for &DataMatrix<T> {
+            type Output = DataMatrix<T>;
+            fn $method(self, rhs: DataMatrix<T>) -> DataMatrix<T> {
+                $borrowed(self, &rhs)
+            }
+        }
+        impl<'b, T: LinearElem> $trait<&'b (dyn Mat<Item = T> + 'b)> for DataMatrix<T> {
+            type Output = DataMatrix<T>;
+            fn $method(self, rhs: &'b (dyn Mat<Item = T> + 'b)) -> DataMatrix<T> {
+                $owned(self, rhs)
+            }
+        }
+        impl<'a, 'b, T: LinearElem> $trait<&'b (dyn Mat<Item = T> + 'b)> for &'a (dyn Mat<Item = T> + 'a) {
+            type Output = DataMatrix<T>;
+            fn $method(self, rhs: &'b (dyn Mat<Item = T> + 'b)) -> DataMatrix<T> {
+                $borrowed(self, rhs)
+            }
+        }
+    };
+}
+
+impl_binary_op!(
+    Add,
+    add,
+    |a, b: &dyn Mat<Item = T>| add_to(a, b),
+    |a: &dyn Mat<Item = T>, b: &dyn Mat<Item = T>| {
+        assert_same_dimensions(a, b);
+        add_to(a.clone_data(), b)
+    }
+);
+impl_binary_op!(
+    Sub,
+    sub,
+    |a, b: &dyn Mat<Item = T>| sub_from(a, b),
+    |a: &dyn Mat<Item = T>, b: &dyn Mat<Item = T>| {
+        assert_same_dimensions(a, b);
+        sub_from(a.clone_data(), b)
+    }
+);
+impl_binary_op!(
+    Mul,
+    mul,
+    |a: DataMatrix<T>, b: &dyn Mat<Item = T>| dot(&a, b),
+    |a: &dyn Mat<Item = T>, b: &dyn Mat<Item = T>| dot(a, b)
+);
+
+impl<T: LinearElem> Neg for DataMatrix<T> {
+    type Output = DataMatrix<T>;
+    fn neg(self) -> DataMatrix<T> {
+        neg(self)
+    }
+}
+impl<T: LinearElem> Neg for &DataMatrix<T> {
+    type Output = DataMatrix<T>;
+    fn neg(self) -> DataMatrix<T> {
+        neg(self.clone_data())
+    }
+}
+impl<'a, T: LinearElem> Neg for &'a (dyn Mat<Item = T> + 'a) {
+    type Output = DataMatrix<T>;
+    fn neg(self) -> DataMatrix<T> {
+        neg(self.clone_data())
+    }
+}
+
+impl<T: LinearElem> Mul<T> for DataMatrix<T> {
+    type Output = DataMatrix<T>;
+    fn mul(mut self, rhs: T) -> DataMatrix<T> {
+        self.scale(&rhs);
+        self
+    }
+}
+impl<T: LinearElem> Mul<T> for &DataMatrix<T> {
+    type Output = DataMatrix<T>;
+    fn mul(self, rhs: T) -> DataMatrix<T> {
+        self.clone_data() * rhs
+    }
+}
+impl<'a, T: LinearElem> Mul<T> for &'a (dyn Mat<Item = T> + 'a) {
+    type Output = DataMatrix<T>;
+    fn mul(self, rhs: T) -> DataMatrix<T> {
+        self.clone_data() * rhs
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::SliceMatrix;
+    use mat_macro::mat_;
+
+    #[test]
+    fn test_ops() {
+        let a: DataMatrix<i32> = mat_![1 2; 3 4;];
+        let b: DataMatrix<i32> = mat_![4 3; 2 1;];
+
+        assert_eq!(&a + &b, mat_![5 5; 5 5;]);
+        assert_eq!(a.clone_data() - &b, mat_![(-3) (-1); 1 3;]);
+        assert_eq!(&a * &b, mat_![8 5; 20 13;]);
+        assert_eq!(-&a, mat_![(-1) (-2); (-3) (-4);]);
+        assert_eq!(a.clone_data() * 2, mat_![2 4; 6 8;]);
+        assert_eq!((&a * &b - b.clone_data() * 2) * a.clone_data(), mat_![(-3) (-4); 49 76;]);
+
+        let s = SliceMatrix::new(&a, 0, 2, 1, 1).unwrap();
+        let (s, a): (&dyn Mat<Item = i32>, &dyn Mat<Item = i32>) = (&s, &a);
+        assert_eq!(a * s, mat_![10; 22;]);
+        assert_eq!(-s + s, mat_![0; 0;]);
+        assert_eq!(s * 3, mat_![6; 12;]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Need dimension (2, 2), got (1, 2)")]
+    fn test_ops_strict() {
+        // `Mat::add` would repeat the row
+        let a: DataMatrix<i32> = mat_![1 2; 3 4;];
+        let b: DataMatrix<i32> = mat_![1 2;];
+        let _ = a + b;
+    }
+}
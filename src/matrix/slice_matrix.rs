@@ -14,7 +14,7 @@ pub struct SliceMatrix<'a, T>
 where
     T: LinearElem,
 {
-    pub origin: *mut dyn Mat<Item = T>,
+    pub origin: *mut (dyn Mat<Item = T> + 'a),
     row_begin: usize,
     col_begin: usize,
     rows: usize,
@@ -41,7 +41,7 @@ where
         col_begin: usize,
         cols: usize,
     ) -> Self {
-        let origin = origin as *const dyn Mat<Item = T> as *mut dyn Mat<Item = T>;
+        let origin = origin as *const (dyn Mat<Item = T> + 'a) as *mut (dyn Mat<Item = T> + 'a);
         SliceMatrix {
             origin,
             row_begin,
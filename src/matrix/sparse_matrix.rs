@@ -0,0 +1,552 @@
+use super::{DataMatrix, Mat};
+use crate::element::LinearElem;
+use crate::error::MatError;
+use MatError::*;
+
+/// Only exact zeros are left out, as [`is_add_zero`](crate::element::AddZero::is_add_zero) of floats has a tolerance
+fn is_zero<T: LinearElem>(x: &T) -> bool {
+    *x == T::add_zero()
+}
+
+/// A matrix storing only its non-zero elements, in compressed sparse row (CSR) form
+///
+/// Transposing only flips a flag, so a transposed [`SparseMatrix`] is stored in compressed
+/// sparse col (CSC) form of the logical matrix. Use [`to_csr`](SparseMatrix::to_csr) or
+/// [`to_csc`](SparseMatrix::to_csc) to actually convert the layout.
+///
+/// Reading an element outside the pattern gives a shared zero, while writing to it through
+/// `get_mut` inserts the element into the pattern, which takes O(nnz) time. Prefer building
+/// the matrix with [`from_triplets`](SparseMatrix::from_triplets)
+///
+/// Arithmetic of [`Mat`], such as [`Mat::add`] and [`Mat::sub`], goes through the dense
+/// defaults and returns a [`DataMatrix`], while `add_assign` inserts every element into the
+/// pattern. Use [`add_sparse`](SparseMatrix::add_sparse), [`sub_sparse`](SparseMatrix::sub_sparse)
+/// and [`dot_sparse`](SparseMatrix::dot_sparse) to stay sparse
+pub struct SparseMatrix<T>
+where
+    T: LinearElem,
+{
+    rows: usize,
+    cols: usize,
+    /// Row `i` is stored in `indptr[i]..indptr[i + 1]` of `indices` and `data`
+    indptr: Vec<usize>,
+    /// Col of each element, increasing within each row
+    indices: Vec<usize>,
+    data: Vec<T>,
+    /// Elements out of the pattern refer to this
+    zero: T,
+    is_transposed: bool,
+}
+
+impl<T> SparseMatrix<T>
+where
+    T: LinearElem,
+{
+    /// A `(rows, cols)` matrix of zeros, with nothing stored
+    pub fn new(rows: usize, cols: usize) -> Self {
+        SparseMatrix {
+            rows,
+            cols,
+            indptr: vec![0; rows + 1],
+            indices: Vec::new(),
+            data: Vec::new(),
+            zero: T::add_zero(),
+            is_transposed: false,
+        }
+    }
+
+    /// Build a matrix from `(i, j, value)` triplets, in any order
+    ///
+    /// Values at the same position are summed up, and zeros are not stored. Fails with
+    /// [`MatError::IndexError`] if some position is out of range
+    pub fn from_triplets(rows: usize, cols: usize, mut triplets: Vec<(usize, usize, T)>) -> Result<Self, MatError> {
+        if let Some(&(i, j, _)) = triplets.iter().find(|(i, j, _)| *i >= rows || *j >= cols) {
+            return Err(IndexError {
+                dim: (rows, cols),
+                i,
+                j,
+                mutable: false,
+            });
+        }
+        triplets.sort_by_key(|(i, j, _)| (*i, *j));
+
+        let mut result = Self::new(rows, cols);
+        let mut last: Option<(usize, usize)> = None;
+        for (i, j, x) in triplets {
+            if last == Some((i, j)) {
+                *result.data.last_mut().unwrap() += &x;
+            } else {
+                result.indices.push(j);
+                result.data.push(x);
+                result.indptr[i + 1] += 1;
+                last = Some((i, j));
+            }
+        }
+        for i in 0..rows {
+            result.indptr[i + 1] += result.indptr[i];
+        }
+        result.prune();
+        Ok(result)
+    }
+
+    /// Build a matrix from CSR arrays, where row `i` has elements `data[k]` at cols `indices[k]`
+    /// for `k` in `indptr[i]..indptr[i + 1]`
+    ///
+    /// Cols in a row may be in any order. Fails with [`MatError::BadSparseFormat`] if the
+    /// arrays don't fit together
+    pub fn from_csr(
+        rows: usize,
+        cols: usize,
+        indptr: Vec<usize>,
+        indices: Vec<usize>,
+        data: Vec<T>,
+    ) -> Result<Self, MatError> {
+        if indptr.len() != rows + 1 {
+            return Err(BadSparseFormat("`indptr` should have one more element than rows"));
+        }
+        if indices.len() != data.len() || indptr[rows] != data.len() {
+            return Err(BadSparseFormat("`indices` and `data` should have `indptr[rows]` elements"));
+        }
+        if indptr[0] != 0 || indptr.windows(2).any(|w| w[0] > w[1]) {
+            return Err(BadSparseFormat("`indptr` should start from 0 and never decrease"));
+        }
+
+        let mut triplets = Vec::with_capacity(data.len());
+        let mut entries = indices.into_iter().zip(data);
+        for i in 0..rows {
+            for _ in indptr[i]..indptr[i + 1] {
+                let (j, x) = entries.next().unwrap();
+                triplets.push((i, j, x));
+            }
+        }
+        Self::from_triplets(rows, cols, triplets)
+    }
+
+    /// Build a matrix from CSC arrays, the same as [`from_csr`](SparseMatrix::from_csr) with
+    /// rows and cols exchanged
+    ///
+    /// The result is kept in CSC form, that is a transposed CSR
+    pub fn from_csc(
+        rows: usize,
+        cols: usize,
+        indptr: Vec<usize>,
+        indices: Vec<usize>,
+        data: Vec<T>,
+    ) -> Result<Self, MatError> {
+        Ok(Self::from_csr(cols, rows, indptr, indices, data)?.transposed())
+    }
+
+    /// Collect the non-zero elements of any [`Mat`]
+    pub fn from_dense(mat: &dyn Mat<Item = T>) -> Self {
+        let mut result = Self::new(mat.rows(), mat.cols());
+        for i in 0..mat.rows() {
+            for j in 0..mat.cols() {
+                let x = unsafe { mat.get_unchecked(i, j) };
+                if !is_zero(x) {
+                    result.indices.push(j);
+                    result.data.push(x.clone());
+                }
+            }
+            result.indptr[i + 1] = result.data.len();
+        }
+        result
+    }
+
+    /// Number of stored elements
+    pub fn nnz(&self) -> usize {
+        self.data.len()
+    }
+
+    /// The raw `(indptr, indices, data)` arrays, which are CSR if not transposed, or CSC if
+    /// transposed
+    pub fn raw_parts(&self) -> (&[usize], &[usize], &[T]) {
+        (&self.indptr, &self.indices, &self.data)
+    }
+
+    /// Iterate over stored elements as `(i, j, value)`, with transpose taken into consideration
+    pub fn iter_nonzero(&self) -> impl Iterator<Item = (usize, usize, &T)> {
+        (0..self.rows).flat_map(move |i| {
+            (self.indptr[i]..self.indptr[i + 1]).map(move |k| {
+                let j = self.indices[k];
+                if self.is_transposed {
+                    (j, i, &self.data[k])
+                } else {
+                    (i, j, &self.data[k])
+                }
+            })
+        })
+    }
+
+    /// The same matrix, stored in CSR form
+    pub fn to_csr(&self) -> Self {
+        if self.is_transposed {
+            self.transpose_storage()
+        } else {
+            self.clone()
+        }
+    }
+
+    /// The same matrix, stored in CSC form
+    pub fn to_csc(&self) -> Self {
+        if self.is_transposed {
+            self.clone()
+        } else {
+            self.transpose_storage()
+        }
+    }
+
+    /// Same matrix with the raw storage transposed, by counting sort
+    fn transpose_storage(&self) -> Self {
+        let mut indptr = vec![0; self.cols + 1];
+        for &j in self.indices.iter() {
+            indptr[j + 1] += 1;
+        }
+        for j in 0..self.cols {
+            indptr[j + 1] += indptr[j];
+        }
+
+        let mut next = indptr.clone();
+        let mut slots: Vec<Option<(usize, T)>> = vec![None; self.nnz()];
+        for i in 0..self.rows {
+            for k in self.indptr[i]..self.indptr[i + 1] {
+                let j = self.indices[k];
+                slots[next[j]] = Some((i, self.data[k].clone()));
+                next[j] += 1;
+            }
+        }
+        let (indices, data) = slots.into_iter().map(|x| x.unwrap()).unzip();
+
+        SparseMatrix {
+            rows: self.cols,
+            cols: self.rows,
+            indptr,
+            indices,
+            data,
+            zero: T::add_zero(),
+            is_transposed: !self.is_transposed,
+        }
+    }
+
+    /// Remove stored zeros
+    fn prune(&mut self) {
+        let mut k = 0;
+        let mut begin = 0;
+        for i in 0..self.rows {
+            let end = self.indptr[i + 1];
+            for p in begin..end {
+                if !is_zero(&self.data[p]) {
+                    self.indices.swap(k, p);
+                    self.data.swap(k, p);
+                    k += 1;
+                }
+            }
+            begin = end;
+            self.indptr[i + 1] = k;
+        }
+        self.indices.truncate(k);
+        self.data.truncate(k);
+    }
+
+    /// Merge two CSR matrices of the same dimensions row by row, by `f(a, b)` on elements in
+    /// either pattern
+    fn merge(a: &Self, b: &Self, f: impl Fn(&T, &T) -> T) -> Self {
+        let mut result = Self::new(a.rows, a.cols);
+        for i in 0..a.rows {
+            let (mut p, mut q) = (a.indptr[i], b.indptr[i]);
+            while p < a.indptr[i + 1] || q < b.indptr[i + 1] {
+                let ja = a.indices.get(p).filter(|_| p < a.indptr[i + 1]);
+                let jb = b.indices.get(q).filter(|_| q < b.indptr[i + 1]);
+                let (j, x) = match (ja, jb) {
+                    (Some(&ja), Some(&jb)) if ja == jb => {
+                        p += 1;
+                        q += 1;
+                        (ja, f(&a.data[p - 1], &b.data[q - 1]))
+                    }
+                    (Some(&ja), jb) if jb.is_none_or(|&jb| ja < jb) => {
+                        p += 1;
+                        (ja, f(&a.data[p - 1], &a.zero))
+                    }
+                    (_, Some(&jb)) => {
+                        q += 1;
+                        (jb, f(&b.zero, &b.data[q - 1]))
+                    }
+                    _ => unreachable!(),
+                };
+                if !is_zero(&x) {
+                    result.indices.push(j);
+                    result.data.push(x);
+                }
+            }
+            result.indptr[i + 1] = result.data.len();
+        }
+        result
+    }
+
+    fn check_same_dimension(&self, rhs: &Self) -> Result<(), MatError> {
+        if self.dimensions() != rhs.dimensions() {
+            return Err(InconsistentDimension {
+                need: self.dimensions(),
+                got: rhs.dimensions(),
+            });
+        }
+        Ok(())
+    }
+
+    /// `self + rhs`, touching only the stored elements
+    pub fn add_sparse(&self, rhs: &Self) -> Result<Self, MatError> {
+        self.check_same_dimension(rhs)?;
+        Ok(Self::merge(&self.to_csr(), &rhs.to_csr(), |a, b| a.clone() + b))
+    }
+
+    /// `self - rhs`, touching only the stored elements
+    pub fn sub_sparse(&self, rhs: &Self) -> Result<Self, MatError> {
+        self.check_same_dimension(rhs)?;
+        Ok(Self::merge(&self.to_csr(), &rhs.to_csr(), |a, b| a.clone() - b))
+    }
+
+    /// `self` dot `rhs` where both are sparse, giving a sparse result
+    ///
+    /// Each row of the result is accumulated from rows of `rhs` selected by the row of `self`,
+    /// so the time taken is proportional to the number of multiplications of non-zeros
+    pub fn dot_sparse(&self, rhs: &Self) -> Result<Self, MatError> {
+        if self.cols() != rhs.rows() {
+            return Err(InconsistentDimension {
+                need: (self.cols(), 0),
+                got: rhs.dimensions(),
+            });
+        }
+
+        let (a, b) = (self.to_csr(), rhs.to_csr());
+        let mut result = Self::new(a.rows, b.cols);
+        // position of each col in `row`, if it's there
+        let mut position: Vec<Option<usize>> = vec![None; b.cols];
+        let mut row: Vec<(usize, T)> = Vec::new();
+        for i in 0..a.rows {
+            for p in a.indptr[i]..a.indptr[i + 1] {
+                let (k, x) = (a.indices[p], &a.data[p]);
+                for q in b.indptr[k]..b.indptr[k + 1] {
+                    let j = b.indices[q];
+                    let y = x.clone() * &b.data[q];
+                    match position[j] {
+                        Some(t) => row[t].1 += &y,
+                        None => {
+                            position[j] = Some(row.len());
+                            row.push((j, y));
+                        }
+                    }
+                }
+            }
+            row.sort_by_key(|(j, _)| *j);
+            for (j, x) in row.drain(..) {
+                position[j] = None;
+                if !is_zero(&x) {
+                    result.indices.push(j);
+                    result.data.push(x);
+                }
+            }
+            result.indptr[i + 1] = result.data.len();
+        }
+        Ok(result)
+    }
+}
+
+impl<T> Clone for SparseMatrix<T>
+where
+    T: LinearElem,
+{
+    fn clone(&self) -> Self {
+        SparseMatrix {
+            rows: self.rows,
+            cols: self.cols,
+            indptr: self.indptr.clone(),
+            indices: self.indices.clone(),
+            data: self.data.clone(),
+            zero: T::add_zero(),
+            is_transposed: self.is_transposed,
+        }
+    }
+}
+
+impl<T> Mat for SparseMatrix<T>
+where
+    T: LinearElem,
+{
+    type Item = T;
+
+    fn is_transposed(&self) -> bool {
+        self.is_transposed
+    }
+    fn transpose(&mut self) {
+        self.is_transposed = !self.is_transposed;
+    }
+    fn rows_raw(&self) -> usize {
+        self.rows
+    }
+    fn cols_raw(&self) -> usize {
+        self.cols
+    }
+
+    unsafe fn get_unchecked_raw(&self, i: usize, j: usize) -> &T {
+        let (begin, end) = (self.indptr[i], self.indptr[i + 1]);
+        match self.indices[begin..end].binary_search(&j) {
+            Ok(k) => &self.data[begin + k],
+            Err(_) => &self.zero,
+        }
+    }
+    /// Elements out of the pattern are inserted as zeros first
+    unsafe fn get_mut_unchecked_raw(&mut self, i: usize, j: usize) -> &mut T {
+        let (begin, end) = (self.indptr[i], self.indptr[i + 1]);
+        let k = match self.indices[begin..end].binary_search(&j) {
+            Ok(k) => begin + k,
+            Err(k) => {
+                self.indices.insert(begin + k, j);
+                self.data.insert(begin + k, T::add_zero());
+                for p in self.indptr[i + 1..].iter_mut() {
+                    *p += 1;
+                }
+                begin + k
+            }
+        };
+        &mut self.data[k]
+    }
+
+    /// Only stored elements are scaled
+    fn scale(&mut self, s: &T) -> &mut Self {
+        for x in self.data.iter_mut() {
+            *x *= s;
+        }
+        self
+    }
+
+    /// Only stored elements are mapped, and those mapped to zero are removed from the pattern
+    fn map_inplace<F>(&mut self, f: F)
+    where
+        Self: Sized,
+        F: FnMut(&mut T),
+    {
+        self.data.iter_mut().for_each(f);
+        self.prune();
+    }
+
+    /// Only stored elements are multiplied, so this takes `O(nnz * rhs.cols())`
+    unsafe fn dot_unchecked(&self, rhs: &dyn Mat<Item = T>) -> DataMatrix<T> {
+        let mut result = DataMatrix::zeros(self.rows(), rhs.cols());
+        for (i, k, x) in self.iter_nonzero() {
+            for j in 0..rhs.cols() {
+                *result.get_mut_unchecked(i, j) += &(x.clone() * rhs.get_unchecked(k, j));
+            }
+        }
+        result
+    }
+
+    fn clone_data(&self) -> DataMatrix<T> {
+        let mut result = DataMatrix::zeros(self.rows(), self.cols());
+        for (i, j, x) in self.iter_nonzero() {
+            unsafe { *result.get_mut_unchecked(i, j) = x.clone() }
+        }
+        result
+    }
+}
+
+impl<T: LinearElem> PartialEq for SparseMatrix<T> {
+    /// Equal as matrices, regardless of layout or stored zeros
+    fn eq(&self, other: &Self) -> bool {
+        self.dimensions() == other.dimensions() && self.sub_sparse(other).is_ok_and(|d| d.nnz() == 0)
+    }
+}
+
+mod display {
+    use super::super::mat_print_buf;
+    use super::*;
+    use std::fmt::{Debug, Display};
+
+    impl<T> Display for SparseMatrix<T>
+    where
+        T: LinearElem + Display,
+    {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            mat_print_buf(self, f)
+        }
+    }
+
+    impl<T> Debug for SparseMatrix<T>
+    where
+        T: LinearElem + Display,
+    {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            mat_print_buf(self, f)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use mat_macro::mat_;
+
+    fn sample() -> SparseMatrix<i32> {
+        SparseMatrix::from_triplets(3, 4, vec![(2, 1, 5), (0, 0, 1), (0, 3, 2), (2, 1, 1), (1, 2, 0)]).unwrap()
+    }
+
+    #[test]
+    fn test_sparse_build() {
+        let a = sample();
+        assert_eq!(a.nnz(), 3);
+        assert_eq!(
+            a.clone_data(),
+            mat_![
+                1 0 0 2;
+                0 0 0 0;
+                0 6 0 0;
+            ]
+        );
+        assert_eq!(SparseMatrix::from_dense(&a.clone_data()), a);
+        assert!(SparseMatrix::from_triplets(3, 4, vec![(3, 0, 1)]).is_err());
+
+        let csr = SparseMatrix::from_csr(3, 4, vec![0, 2, 2, 3], vec![3, 0, 1], vec![2, 1, 6]).unwrap();
+        assert_eq!(csr, a);
+        let csc = SparseMatrix::from_csc(3, 4, vec![0, 1, 2, 2, 3], vec![0, 2, 0], vec![1, 6, 2]).unwrap();
+        assert!(csc.is_transposed());
+        assert_eq!(csc, a);
+        assert_eq!(csc.to_csr().raw_parts(), a.raw_parts());
+        assert!(SparseMatrix::from_csr(3, 4, vec![0, 2, 1, 3], vec![3, 0, 1], vec![2, 1, 6]).is_err());
+    }
+
+    #[test]
+    fn test_sparse_mat() {
+        let mut a = sample();
+        assert_eq!(*a.get(2, 1).unwrap(), 6);
+        assert_eq!(*a.get(1, 1).unwrap(), 0);
+        *a.get_mut(1, 1).unwrap() = 7;
+        assert_eq!(a.nnz(), 4);
+        assert_eq!(*a.get(1, 1).unwrap(), 7);
+
+        let t = a.clone().transposed();
+        assert_eq!(t.dimensions(), (4, 3));
+        assert_eq!(*t.get(1, 2).unwrap(), 6);
+        assert_eq!(t.to_csr().clone_data(), a.clone_data().transposed().clone_data());
+
+        let x: DataMatrix<i32> = mat_![1; 2; 3; 4;];
+        assert_eq!(a.dot(&x).unwrap(), a.clone_data().dot(&x).unwrap());
+        assert_eq!(t.dot(&a.clone_data()).unwrap(), t.clone_data().dot(&a).unwrap());
+    }
+
+    #[test]
+    fn test_sparse_ops() {
+        let a = sample();
+        let t = a.clone().transposed();
+        let (da, dt) = (a.clone_data(), t.clone_data());
+
+        assert_eq!(a.dot_sparse(&t).unwrap().clone_data(), da.dot(&dt).unwrap());
+        assert_eq!(t.dot_sparse(&a).unwrap().clone_data(), dt.dot(&da).unwrap());
+        assert!(a.dot_sparse(&a).is_err());
+
+        let b = SparseMatrix::from_triplets(3, 4, vec![(0, 0, -1), (1, 1, 3)]).unwrap();
+        let sum = a.add_sparse(&b).unwrap();
+        // the cancelled element isn't stored
+        assert_eq!(sum.nnz(), 3);
+        assert_eq!(sum.clone_data(), da.add(&b.clone_data()));
+        assert_eq!(a.sub_sparse(&b).unwrap().clone_data(), da.sub(&b.clone_data()));
+        assert!(a.add_sparse(&t).is_err());
+    }
+}
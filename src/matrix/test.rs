@@ -1,4 +1,5 @@
 use super::*;
+use crate::{Complex, Rational};
 extern crate mat_macro;
 use mat_macro::mat_;
 
@@ -57,6 +58,64 @@ fn test_swap() {
     assert_eq!(a, mat_![3 4; 1 2;]);
 }
 
+#[test]
+fn test_try_add() {
+    let a: DataMatrix<i32> = mat_![1 2; 3 4;];
+    let b: DataMatrix<i32> = mat_![1 2 3; 4 5 6; 7 8 9;];
+    assert_eq!(a.try_add(&a).unwrap(), mat_![2 4; 6 8;]);
+    assert_eq!(a.try_sub(&a).unwrap(), mat_![0 0; 0 0;]);
+    assert!(matches!(a.try_add(&b), Err(InconsistentDimension { need: (2, 2), got: (3, 3) })));
+    assert!(matches!(a.try_sub(&mat_![1 2;]), Err(InconsistentDimension { .. })));
+}
+
+#[test]
+fn test_broadcast() {
+    let a: DataMatrix<i32> = mat_![1 2; 3 4;];
+    assert_eq!(a.broadcast_add(&mat_![10 20;]).unwrap(), mat_![11 22; 13 24;]);
+    assert_eq!(a.broadcast_sub(&mat_![1; 3;]).unwrap(), mat_![0 1; 0 1;]);
+    assert_eq!(a.broadcast_add(&mat_![1;]).unwrap(), mat_![2 3; 4 5;]);
+    assert_eq!(a.broadcast_add(&a).unwrap(), mat_![2 4; 6 8;]);
+    let b: DataMatrix<i32> = mat_![1 2 3; 4 5 6; 7 8 9;];
+    assert!(matches!(a.broadcast_add(&b), Err(InconsistentDimension { need: (2, 2), got: (3, 3) })));
+    assert!(a.broadcast_add(&mat_![1 2 3;]).is_err());
+}
+
+#[test]
+fn test_map() {
+    let mut a: DataMatrix<i32> = mat_![1 2; 3 4;];
+    assert_eq!(a.map(|x| x * x), mat_![1 4; 9 16;]);
+    let b: DataMatrix<f64> = a.map(|x| *x as f64 / 2.0);
+    assert_eq!(b, mat_![0.5 1.0; 1.5 2.0;]);
+
+    a.transpose();
+    a.map_inplace(|x| *x -= 1);
+    assert_eq!(a, mat_![0 2; 1 3;]);
+
+    let c = a.zip_with(&b, |x, y| Complex(*x as f64, *y)).unwrap();
+    assert_eq!(c, mat_![(Complex(0.0, 0.5)) (Complex(2.0, 1.0)); (Complex(1.0, 1.5)) (Complex(3.0, 2.0));]);
+    assert!(matches!(a.zip_with(&mat_![1;], |x, y| x + y), Err(InconsistentDimension { .. })));
+}
+
+#[test]
+fn test_hadamard() {
+    let a: DataMatrix<Rational> = mat_![1 2; 3 4;].convert();
+    let b: DataMatrix<Rational> = mat_![2 2; 1 8;].convert();
+    assert_eq!(a.hadamard(&b).unwrap(), mat_![2 4; 3 32;].convert());
+    assert_eq!(a.hadamard_div(&b).unwrap().hadamard(&b).unwrap(), a);
+    let c: DataMatrix<f64> = mat_![1.0 3.0; 2.0 1.0;];
+    assert_eq!(c.hadamard_div(&mat_![2.0 4.0; 0.5 1.0;]).unwrap(), mat_![0.5 0.75; 4.0 1.0;]);
+    assert!(a.hadamard(&b.clone_data().transposed().row(0).unwrap()).is_err());
+}
+
+#[test]
+fn test_convert_with() {
+    let a: DataMatrix<i32> = mat_![1 2 3; 4 5 6;].transposed();
+    let b = a.clone_data().transposed().convert_with(|x| Complex(0.0, x as f64)).transposed();
+    assert_eq!(b, mat_![(Complex(0.0, 1.0)) (Complex(0.0, 4.0)); (Complex(0.0, 2.0)) (Complex(0.0, 5.0)); (Complex(0.0, 3.0)) (Complex(0.0, 6.0));]);
+    let c: DataMatrix<i64> = a.convert();
+    assert_eq!(c, mat_![1 4; 2 5; 3 6;]);
+}
+
 #[test]
 fn test_dot() {
     let a: DataMatrix<i32> = mat_![1 0; 0 1;];
@@ -64,6 +123,100 @@ fn test_dot() {
     assert_eq!(a.dot(&b).unwrap(), mat_![1 2; 3 4;]);
 }
 
+#[test]
+fn test_dot_tiled() {
+    // larger than a tile in every direction, compared with the generic path through a slice
+    let a: DataMatrix<i64> = DataMatrix::new((0..20 * 150).map(|x| x % 13 - 6).collect(), 20, 150).unwrap();
+    let b: DataMatrix<i64> = DataMatrix::new((0..150 * 270).map(|x| x % 11 - 5).collect(), 150, 270).unwrap();
+    let generic = SliceMatrix::new(&a, 0, 20, 0, 150).unwrap().dot(&b).unwrap();
+    assert_eq!(a.dot(&b).unwrap(), generic);
+
+    let at = a.clone_data().transposed().clone_data().transposed();
+    assert_eq!(at.dot(&b).unwrap(), generic);
+    let (bt, a_t) = (b.clone_data().transposed(), a.clone_data().transposed());
+    let generic = SliceMatrix::new(&bt, 0, 270, 0, 150).unwrap().dot(&a_t).unwrap();
+    assert_eq!(bt.dot(&a_t).unwrap(), generic);
+
+    let empty: DataMatrix<i64> = DataMatrix::zeros(3, 0);
+    assert_eq!(empty.dot(&DataMatrix::zeros(0, 2)).unwrap(), DataMatrix::zeros(3, 2));
+}
+
+#[test]
+fn test_row_blocks_mut() {
+    let mut a: DataMatrix<i32> = mat_![
+        1 2 3 4 5;
+        6 7 8 9 10;
+    ]
+    .transposed();
+    let blocks = a.row_blocks_mut(2);
+    assert_eq!(blocks.iter().map(|b| (b.first_row(), b.rows())).collect::<Vec<_>>(), vec![(0, 2), (2, 2), (4, 1)]);
+    for mut block in blocks {
+        for (i, row) in block.rows_mut() {
+            row[1] *= i as i32;
+        }
+    }
+    assert_eq!(a, mat_![1 0; 2 7; 3 16; 4 27; 5 40;]);
+    assert!(DataMatrix::<i32>::zeros(0, 3).row_blocks_mut(2).is_empty());
+}
+
+/// Held by the tests that change the parallel threshold
+#[cfg(feature = "parallel")]
+static THRESHOLD: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+#[test]
+fn test_large_kernels() {
+    // several blocks of rows, split across threads with the `parallel` feature
+    #[cfg(feature = "parallel")]
+    let _lock = THRESHOLD.lock().unwrap();
+    #[cfg(feature = "parallel")]
+    set_parallel_threshold(64);
+    let n = 50;
+    let data: Vec<f64> = (0..n * n).map(|x| ((x * 7919) % 101) as f64 - 50.0).collect();
+    let a = DataMatrix::new(data, n, n).unwrap();
+
+    let generic = SliceMatrix::new(&a, 0, n, 0, n).unwrap().dot(&a).unwrap();
+    assert_eq!(a.dot(&a).unwrap(), generic);
+
+    // row updates on the raw data give the same result as through a slice
+    let e = a.clone_data().eliminated().unwrap();
+    let b = a.clone_data();
+    let s = SliceMatrix::new(&b, 0, n, 0, n).unwrap().eliminated().unwrap();
+    assert_eq!(e.mat, s.mat.clone_data());
+
+    let mut r = a.clone_data();
+    let q = alg::qr(&mut r).unwrap();
+    let qr = q.dot(&r).unwrap();
+    for i in 0..n {
+        for j in 0..n {
+            assert!((qr.get(i, j).unwrap() - a.get(i, j).unwrap()).abs() < 1e-9);
+            if i > j {
+                assert!(r.get(i, j).unwrap().abs() < 1e-9);
+            }
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "parallel")]
+fn test_parallel_matches_serial() {
+    let _lock = THRESHOLD.lock().unwrap();
+    let n = 40;
+    let data: Vec<f64> = (0..n * n).map(|x| ((x * 7919) % 101) as f64 - 50.0).collect();
+    let a = DataMatrix::new(data, n, n).unwrap();
+    let run = || {
+        let mut r = a.clone_data();
+        let q = alg::qr(&mut r).unwrap();
+        (a.dot(&a).unwrap(), a.clone_data().eliminated().unwrap().mat, q, r)
+    };
+
+    set_parallel_threshold(usize::MAX);
+    let serial = run();
+    set_parallel_threshold(0);
+    let parallel = run();
+    set_parallel_threshold(1 << 14);
+    assert_eq!(serial, parallel);
+}
+
 #[test]
 fn test_transpose() {
     let a: DataMatrix<i32> = mat_![
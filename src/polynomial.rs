@@ -1,12 +1,18 @@
 use crate::element::*;
+use crate::error::MatError;
+use crate::matrix::{DataMatrix, Mat};
+use crate::matrix::checked_dot;
 use std::ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign};
 use std::cmp::{PartialEq, Eq};
 
+/// Root finding
+mod roots;
+
 #[macro_export]
 /// Create a [`Polynomial`], coefficients are from low to high
 macro_rules! polynomial {
     ($($x:expr),+ $(,)?) => {
-        $crate::Polynomial { coef: vec![$($x),+] }
+        $crate::Polynomial::new(vec![$($x),+])
     };
 }
 
@@ -21,6 +27,145 @@ where
     pub coef: Vec<T>,
 }
 
+impl<T> Polynomial<T>
+where
+    T: LinearElem,
+{
+    /// Create a polynomial from coefficients from low to high, trailing zeros are trimmed
+    pub fn new(coef: Vec<T>) -> Self {
+        let mut p = Polynomial { coef };
+        p.normalize();
+        p
+    }
+
+    /// Trim trailing zero coefficients, leaving a single zero for the zero polynomial
+    ///
+    /// Arithmetic operations call this on their results, so this is only needed after altering
+    /// `coef` directly
+    pub fn normalize(&mut self) {
+        while self.coef.len() > 1 && self.coef.last().unwrap().is_add_zero() {
+            self.coef.pop();
+        }
+        if self.coef.is_empty() {
+            self.coef.push(T::add_zero());
+        }
+    }
+
+    /// Degree of the polynomial, taking the zero polynomial as degree `0`
+    pub fn degree(&self) -> usize {
+        self.coef.len() - 1
+    }
+
+    /// Coefficient of the highest power
+    pub fn leading_coef(&self) -> &T {
+        self.coef.last().unwrap()
+    }
+
+    /// The formal derivative
+    pub fn derivative(&self) -> Self {
+        let mut k = T::add_zero();
+        let mut coef = Vec::with_capacity(self.coef.len());
+        for c in self.coef.iter().skip(1) {
+            k.add_assign(&T::mul_zero());
+            coef.push(c.clone() * &k);
+        }
+        Polynomial::new(coef)
+    }
+
+    /// Evaluate at `x` by Horner's method
+    pub fn eval(&self, x: &T) -> T {
+        let mut result = T::add_zero();
+        for c in self.coef.iter().rev() {
+            result = result * x + c;
+        }
+        result
+    }
+
+    /// Evaluate at a square matrix `m` by Horner's method, where the constant term is taken as
+    /// a multiple of the identity
+    ///
+    /// Fails with [`MatError::Overflow`] if intermediate results overflow
+    pub fn eval_mat(&self, m: &dyn Mat<Item = T>) -> Result<DataMatrix<T>, MatError> {
+        if !m.is_square() {
+            return Err(MatError::NotSquare { dim: m.dimensions() });
+        }
+        let n = m.rows();
+        let mut result = DataMatrix::zeros(n, n);
+        for (k, c) in self.coef.iter().rev().enumerate() {
+            if k > 0 {
+                result = unsafe { checked_dot(&result, m)? };
+            }
+            for i in 0..n {
+                unsafe {
+                    let x = result.get_unchecked(i, i).checked_add(c).ok_or(MatError::Overflow)?;
+                    *result.get_mut_unchecked(i, i) = x;
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl<T> Polynomial<T>
+where
+    T: LinearElem + Inv,
+{
+    /// Long division, returning `(quotient, remainder)` where the degree of the remainder is
+    /// less than that of `rhs`, or [`None`] if `rhs` is zero
+    ///
+    /// Coefficients should be a field, as the leading coefficient of `rhs` is inverted
+    pub fn div_rem(&self, rhs: &Self) -> Option<(Self, Self)> {
+        if rhs.is_add_zero() {
+            return None;
+        }
+        let d = rhs.degree();
+        if self.degree() < d {
+            return Some((Polynomial::add_zero(), self.clone()));
+        }
+
+        let lead_inv = rhs.leading_coef().clone().inv();
+        let mut rem = self.coef.clone();
+        let mut quot = vec![T::add_zero(); self.degree() - d + 1];
+        for k in (0..quot.len()).rev() {
+            let q = rem[k + d].clone() * &lead_inv;
+            if q.is_add_zero() {
+                continue;
+            }
+            for (j, r) in rhs.coef.iter().enumerate() {
+                rem[k + j].sub_assign(&(q.clone() * r));
+            }
+            // cancelled exactly, even if rounding says otherwise
+            rem[k + d] = T::add_zero();
+            quot[k] = q;
+        }
+
+        Some((Polynomial::new(quot), Polynomial::new(rem)))
+    }
+
+    /// Make the leading coefficient `1`, unless the polynomial is zero
+    pub fn monic(&self) -> Self {
+        if self.is_add_zero() {
+            return self.clone();
+        }
+        let lead_inv = self.leading_coef().clone().inv();
+        Polynomial::new(self.coef.iter().map(|c| c.clone() * &lead_inv).collect())
+    }
+
+    /// Greatest common divisor by Euclid's algorithm, made monic
+    ///
+    /// Zero tests are exact, so this is meant for exact coefficients like
+    /// [`Rational`](crate::Rational)
+    pub fn gcd(&self, rhs: &Self) -> Self {
+        let (mut a, mut b) = (self.clone(), rhs.clone());
+        while !b.is_add_zero() {
+            let (_, r) = a.div_rem(&b).unwrap();
+            a = b;
+            b = r;
+        }
+        a.monic()
+    }
+}
+
 mod display {
     use super::*;
     use std::fmt::{Debug, Display};
@@ -91,6 +236,7 @@ where
         for (r, s) in rhs.coef.iter().zip(self.coef.iter_mut()) {
             s.add_assign(r);
         }
+        self.normalize();
     }
 }
 
@@ -117,6 +263,7 @@ where
         for (r, s) in rhs.coef.iter().zip(self.coef.iter_mut()) {
             s.sub_assign(r);
         }
+        self.normalize();
     }
 }
 
@@ -152,7 +299,7 @@ where
             }
         }
 
-        Polynomial { coef: result }
+        Polynomial::new(result)
     }
 }
 
@@ -194,6 +341,7 @@ where
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::{rational, Rational};
 
     fn a() -> Polynomial<i32> {
         polynomial!(1, 2, 1)
@@ -225,4 +373,69 @@ mod test {
         a.mul_assign(&b());
         assert_eq!(a.coef, vec![1, 3, 3, 1]);
     }
+
+    fn r(coef: &[i32]) -> Polynomial<Rational> {
+        Polynomial::new(coef.iter().map(|&x| rational!(x, 1)).collect())
+    }
+
+    #[test]
+    fn test_normalize() {
+        let c = a().sub(&a());
+        assert!(c.is_add_zero());
+        assert_eq!(c.degree(), 0);
+
+        let c = a().sub(&polynomial!(0, 0, 1));
+        assert_eq!(c.coef, vec![1, 2]);
+        assert_eq!(*c.leading_coef(), 2);
+    }
+
+    #[test]
+    fn test_derivative_eval() {
+        assert_eq!(a().derivative().coef, vec![2, 2]);
+        assert_eq!(a().eval(&3), 16);
+        assert!(polynomial!(5).derivative().is_add_zero());
+    }
+
+    #[test]
+    fn test_div_rem() {
+        // x^3 - 2x^2 - 4 = (x - 3)(x^2 + x + 3) + 5
+        let (q, rem) = r(&[-4, 0, -2, 1]).div_rem(&r(&[-3, 1])).unwrap();
+        assert_eq!(q, r(&[3, 1, 1]));
+        assert_eq!(rem, r(&[5]));
+
+        let (q, rem) = r(&[1, 1]).div_rem(&r(&[0, 0, 1])).unwrap();
+        assert!(q.is_add_zero());
+        assert_eq!(rem, r(&[1, 1]));
+
+        assert!(r(&[1]).div_rem(&r(&[0])).is_none());
+    }
+
+    #[test]
+    fn test_gcd() {
+        // (x - 1)^2 (x + 2) and (x - 1)(x + 3)
+        let p = r(&[2, -3, 0, 1]);
+        let q = r(&[-3, 2, 1]).mul(&r(&[2]));
+        assert_eq!(p.gcd(&q), r(&[-1, 1]));
+        assert_eq!(p.gcd(&r(&[0])), p);
+    }
+
+    #[test]
+    fn test_eval_mat() {
+        use crate::alg::charpoly;
+        use mat_macro::mat_;
+
+        let m: DataMatrix<Rational> = mat_![
+            1 2 0;
+            0 1 3;
+            4 0 2;
+        ]
+        .convert();
+        // Cayley-Hamilton
+        let p = charpoly(&m).unwrap();
+        assert_eq!(p.eval_mat(&m).unwrap(), DataMatrix::zeros(3, 3));
+
+        let id: DataMatrix<Rational> = DataMatrix::identity(3);
+        let p = r(&[1, 1]);
+        assert_eq!(p.eval_mat(&m).unwrap(), m.clone_data().add(&id).clone_data());
+    }
 }
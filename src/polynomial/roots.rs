@@ -0,0 +1,541 @@
+use super::Polynomial;
+use crate::alg::{EigenElem, EigenValueSolver};
+use crate::element::*;
+use crate::error::MatError;
+use crate::rational::{gcd, Integer};
+use crate::{Complex, DataMatrix, Mat, Rational};
+use std::collections::HashSet;
+
+/// Evaluate `coef` at `z` by Horner's method
+fn eval_complex(coef: &[Complex], z: Complex) -> Complex {
+    let mut result = Complex(0.0, 0.0);
+    for c in coef.iter().rev() {
+        result = result * &z + c;
+    }
+    result
+}
+
+/// Group `roots` that are within `tol` (relative to their modulus, if it's larger than `1`) of
+/// each other, returning the mean and size of each group
+fn cluster(roots: Vec<Complex>, tol: f64) -> Vec<(Complex, usize)> {
+    let mut clusters: Vec<(Complex, usize)> = Vec::new();
+    for z in roots {
+        let near = clusters.iter_mut().find(|(c, _)| (*c - &z).normal() <= tol * c.normal().max(1.0));
+        match near {
+            Some((c, m)) => {
+                // running mean
+                let k = *m as f64;
+                *c = (*c * &Complex(k, 0.0) + &z) * &Complex((k + 1.0).recip(), 0.0);
+                *m += 1;
+            }
+            None => clusters.push((z, 1)),
+        }
+    }
+    clusters
+}
+
+impl<T> Polynomial<T>
+where
+    T: EigenElem,
+{
+    /// Companion matrix of the polynomial, whose eigen values are the roots
+    ///
+    /// The polynomial must have a degree of at least `1`
+    fn companion(&self) -> DataMatrix<T> {
+        let n = self.degree();
+        let lead_inv = self.leading_coef().clone().inv();
+        let mut m = DataMatrix::zeros(n, n);
+        unsafe {
+            for j in 0..n {
+                *m.get_mut_unchecked(0, j) = T::add_zero() - &(self.coef[n - 1 - j].clone() * &lead_inv);
+            }
+            for i in 1..n {
+                *m.get_mut_unchecked(i, i - 1) = T::mul_zero();
+            }
+        }
+        m
+    }
+
+    /// Roots as eigen values of the companion matrix, grouped by multiplicity
+    ///
+    /// Roots within `tol` of each other (relatively, for those of modulus over `1`) are taken
+    /// as one multiple root, and their mean, which is more accurate than any of them, is
+    /// returned. A `k`-fold root is only found to about `EPSILON^(1/k)`, so `tol` should be
+    /// larger than that. Simple roots are polished with Newton's method.
+    ///
+    /// The zero polynomial and constants have no roots
+    pub fn roots_with_multiplicity(&self, tol: f64) -> Result<Vec<(Complex, usize)>, MatError> {
+        if self.degree() == 0 {
+            return Ok(Vec::new());
+        }
+
+        let (zs, report) = EigenValueSolver::new(self.companion())?.eigen_values_with_report(0.0, 100 * self.degree());
+        if !report.converged {
+            return Err(MatError::NotConverged {
+                iterations: report.iterations,
+            });
+        }
+
+        let coef: Vec<Complex> = self.coef.iter().map(|c| c.clone().into()).collect();
+        let mut d: Vec<Complex> = Vec::with_capacity(coef.len() - 1);
+        for (k, c) in coef.iter().enumerate().skip(1) {
+            d.push(*c * &Complex(k as f64, 0.0));
+        }
+
+        let mut roots = cluster(zs, tol);
+        for (z, m) in roots.iter_mut() {
+            if *m > 1 {
+                continue;
+            }
+            for _ in 0..3 {
+                let (pz, dz) = (eval_complex(&coef, *z), eval_complex(&d, *z));
+                if dz.normal() == 0.0 {
+                    break;
+                }
+                let next = *z - &(pz / &dz);
+                if eval_complex(&coef, next).normal() >= pz.normal() {
+                    break;
+                }
+                *z = next;
+            }
+        }
+        Ok(roots)
+    }
+
+    /// All roots, each repeated by its multiplicity
+    ///
+    /// Calls [`roots_with_multiplicity`](Polynomial::roots_with_multiplicity) with `tol` being
+    /// `EPSILON^(1/4)`, which separates roots of multiplicity up to `3` or so from close ones
+    pub fn roots(&self) -> Result<Vec<Complex>, MatError> {
+        let roots = self.roots_with_multiplicity(f64::EPSILON.powf(0.25))?;
+        Ok(roots
+            .into_iter()
+            .flat_map(|(z, m)| std::iter::repeat_n(z, m))
+            .collect())
+    }
+}
+
+/// Convergents `p/q` of the continued fraction of `x`, with increasing `q` up to `max_q`,
+/// stopping early if they overflow
+// `Integer` may be `i32`, so the conversion is fallible in general
+#[allow(clippy::unnecessary_fallible_conversions)]
+fn convergents(mut x: f64, max_q: &Integer) -> Vec<(Integer, Integer)> {
+    let mut result = Vec::new();
+    let (mut h, mut h_prev, mut k, mut k_prev) = (1i64, 0i64, 0i64, 1i64);
+    while x.is_finite() && x.abs() < i64::MAX as f64 {
+        let a = x.floor() as i64;
+        let next = a
+            .checked_mul(h)
+            .and_then(|y| y.checked_add(h_prev))
+            .zip(a.checked_mul(k).and_then(|y| y.checked_add(k_prev)));
+        let Some((h_next, k_next)) = next else {
+            break;
+        };
+        (h_prev, h, k_prev, k) = (h, h_next, k, k_next);
+        match (Integer::try_from(h), Integer::try_from(k)) {
+            (Ok(p), Ok(q)) if q <= *max_q => result.push((p, q)),
+            _ => break,
+        }
+
+        let frac = x - a as f64;
+        if frac == 0.0 {
+            break;
+        }
+        x = frac.recip();
+    }
+    result
+}
+
+/// Evaluate at `x`, or [`None`] on overflow
+fn checked_eval(p: &Polynomial<Rational>, x: &Rational) -> Option<Rational> {
+    let mut result = Rational::add_zero();
+    for c in p.coef.iter().rev() {
+        result = result.checked_mul(x)?.checked_add(c)?;
+    }
+    Some(result)
+}
+
+/// Same as [`Polynomial::div_rem`], or [`None`] on overflow. `b` must be non-zero
+fn checked_div_rem(a: &Polynomial<Rational>, b: &Polynomial<Rational>) -> Option<(Polynomial<Rational>, Polynomial<Rational>)> {
+    let d = b.degree();
+    if a.degree() < d {
+        return Some((Polynomial::add_zero(), a.clone()));
+    }
+    let lead = b.leading_coef();
+    let mut rem = a.coef.clone();
+    let mut quot = vec![Rational::add_zero(); a.degree() - d + 1];
+    for k in (0..quot.len()).rev() {
+        let q = rem[k + d].checked_div(lead)?;
+        for (j, r) in b.coef[..d].iter().enumerate() {
+            rem[k + j] = rem[k + j].checked_sub(&q.checked_mul(r)?)?;
+        }
+        rem[k + d] = Rational::add_zero();
+        quot[k] = q;
+    }
+    Some((Polynomial::new(quot), Polynomial::new(rem)))
+}
+
+/// `p` divided by `|lead|`, which keeps the signs of its values. [`None`] on overflow
+fn checked_unit(p: &Polynomial<Rational>) -> Option<Polynomial<Rational>> {
+    let lead = p.leading_coef();
+    let lead = if *lead < Rational::add_zero() { Rational::add_zero().checked_sub(lead)? } else { lead.clone() };
+    let coef = p.coef.iter().map(|c| c.checked_div(&lead)).collect::<Option<Vec<_>>>()?;
+    Some(Polynomial::new(coef))
+}
+
+/// Same as [`Polynomial::derivative`], or [`None`] on overflow
+fn checked_derivative(p: &Polynomial<Rational>) -> Option<Polynomial<Rational>> {
+    let coef = (1..p.coef.len())
+        .map(|k| p.coef[k].checked_mul(&Rational::from(Integer::from(k as i32))))
+        .collect::<Option<Vec<_>>>()?;
+    Some(Polynomial::new(coef))
+}
+
+/// `p / gcd(p, p')` made monic, which has the same roots as `p`, each of multiplicity `1`.
+/// [`None`] on overflow. `p` must not be a constant
+fn square_free(p: &Polynomial<Rational>) -> Option<Polynomial<Rational>> {
+    let (mut a, mut b) = (p.clone(), checked_derivative(p)?);
+    while !b.is_add_zero() {
+        let r = checked_div_rem(&a, &b)?.1;
+        a = b;
+        b = if r.is_add_zero() { r } else { checked_unit(&r)? };
+    }
+    let q = checked_div_rem(p, &a)?.0;
+    let coef = q.coef.iter().map(|c| c.checked_div(q.leading_coef())).collect::<Option<Vec<_>>>()?;
+    Some(Polynomial::new(coef))
+}
+
+/// `p / (x - root)`, or [`None`] on overflow
+fn checked_deflate(p: &Polynomial<Rational>, root: &Rational) -> Option<Polynomial<Rational>> {
+    let mut quot = vec![Rational::add_zero(); p.degree()];
+    let mut carry = Rational::add_zero();
+    for k in (0..p.degree()).rev() {
+        carry = carry.checked_mul(root)?.checked_add(&p.coef[k + 1])?;
+        quot[k] = carry.clone();
+    }
+    Some(Polynomial::new(quot))
+}
+
+/// Constant and (absolute) leading coefficients of `p` scaled to integer coefficients by the lcm
+/// of denominators, or [`None`] on overflow
+fn integer_ends(p: &Polynomial<Rational>) -> Option<(Integer, Integer)> {
+    let mut l = Integer::from(1);
+    for c in p.coef.iter() {
+        let g = gcd(&l, &c.1);
+        l = checked_int(&Rational::from(l.clone() / g), &Rational::from(c.1.clone()))?;
+    }
+    let constant = checked_int(&p.coef[0], &Rational::from(l.clone()))?;
+    let lead = checked_int(p.leading_coef(), &Rational::from(l))?;
+    let zero = Integer::from(0);
+    let lead = if lead < zero { checked_int(&Rational::from(lead), &Rational::from(Integer::from(-1)))? } else { lead };
+    Some((constant, lead))
+}
+
+/// `x * y` for an integer product, or [`None`] on overflow
+fn checked_int(x: &Rational, y: &Rational) -> Option<Integer> {
+    Some(x.checked_mul(y)?.0)
+}
+
+/// Sturm sequence of a square-free `p`, each scaled by a positive constant, or [`None`] on
+/// overflow
+fn sturm(p: &Polynomial<Rational>) -> Option<Vec<Polynomial<Rational>>> {
+    let mut seq = vec![checked_unit(p)?, checked_unit(&checked_derivative(p)?)?];
+    loop {
+        let n = seq.len();
+        let r = checked_div_rem(&seq[n - 2], &seq[n - 1])?.1;
+        if r.is_add_zero() {
+            return Some(seq);
+        }
+        let neg = r.coef.iter().map(|c| Rational::add_zero().checked_sub(c)).collect::<Option<Vec<_>>>()?;
+        seq.push(checked_unit(&Polynomial::new(neg))?);
+    }
+}
+
+/// Sign changes of the Sturm sequence at `x`, skipping zeros
+fn sign_changes(seq: &[Polynomial<Rational>], x: &Rational) -> Option<usize> {
+    let zero = Rational::add_zero();
+    let mut changes = 0;
+    let mut last: Option<bool> = None;
+    for p in seq {
+        let y = checked_eval(p, x)?;
+        if y == zero {
+            continue;
+        }
+        let positive = y > zero;
+        if last.is_some_and(|s| s != positive) {
+            changes += 1;
+        }
+        last = Some(positive);
+    }
+    Some(changes)
+}
+
+/// Number of distinct real roots in `(a, b]`
+fn count_roots(seq: &[Polynomial<Rational>], a: &Rational, b: &Rational) -> Option<usize> {
+    Some(sign_changes(seq, a)? - sign_changes(seq, b)?)
+}
+
+/// The fraction with the least denominator in `[a, b]`, where `a < b`, or [`None`] on overflow
+fn simplest_between(a: &Rational, b: &Rational) -> Option<Rational> {
+    let n = Rational::from(IntegerElem::checked_div_euclid(&a.0, &a.1)?);
+    if n == *a {
+        return Some(n);
+    }
+    let next = n.checked_add(&Rational::mul_zero())?;
+    if next <= *b {
+        return Some(next);
+    }
+    // `a` and `b` lie strictly between `n` and `n + 1`
+    let lo = b.checked_sub(&n)?.inv();
+    let hi = a.checked_sub(&n)?.inv();
+    n.checked_add(&simplest_between(&lo, &hi)?.inv())
+}
+
+/// Rational roots of a square-free `p`, found exactly
+///
+/// The real roots are isolated by the Sturm sequence, then each interval is bisected until it's
+/// shorter than `1/lead^2`, where `lead` is the leading coefficient of `p` scaled to integers.
+/// Two fractions with denominators up to `lead` are further apart than that, so the root can only
+/// be the fraction with the least denominator in the interval. [`None`] on overflow
+fn exact_rational_roots(p: &Polynomial<Rational>) -> Option<Vec<Rational>> {
+    let seq = sturm(p)?;
+    let zero = Rational::add_zero();
+    let one = Rational::mul_zero();
+    let two = Rational::from(2);
+    let lead = Rational::from(integer_ends(p)?.1);
+
+    // Cauchy's bound, so that every real root is in `(-bound, bound]`
+    let mut bound = zero.clone();
+    for c in p.coef[..p.degree()].iter() {
+        let x = c.checked_div(p.leading_coef())?;
+        let x = if x < zero { zero.checked_sub(&x)? } else { x };
+        bound = bound.max(x);
+    }
+    let bound = bound.checked_add(&one)?;
+
+    let mut result = Vec::new();
+    let mut intervals = vec![(zero.checked_sub(&bound)?, bound)];
+    while let Some((mut a, mut b)) = intervals.pop() {
+        let n = count_roots(&seq, &a, &b)?;
+        if n == 0 {
+            continue;
+        }
+        let mid = a.checked_add(&b)?.checked_div(&two)?;
+        if n > 1 {
+            intervals.push((a, mid.clone()));
+            intervals.push((mid, b));
+            continue;
+        }
+
+        while b.checked_sub(&a)?.checked_mul(&lead)?.checked_mul(&lead)? >= one {
+            let mid = a.checked_add(&b)?.checked_div(&two)?;
+            if count_roots(&seq, &a, &mid)? == 1 {
+                b = mid;
+            } else {
+                a = mid;
+            }
+        }
+        let x = simplest_between(&a, &b)?;
+        if x > a && Rational::from(x.1.clone()) <= lead && checked_eval(p, &x)? == zero {
+            result.push(x);
+        }
+    }
+    Some(result)
+}
+
+impl Polynomial<Rational> {
+    /// Exact rational roots, returning the roots with their multiplicities, and the factor left
+    /// after dividing them out, which has no rational roots
+    ///
+    /// Roots of the square-free part are first approximated in `f64`, then each is snapped to
+    /// the nearby fractions `p/q` of its continued fraction. By the rational root theorem, a
+    /// root of the polynomial scaled to integer coefficients has `p` dividing the constant term
+    /// and `q` dividing the leading coefficient, and only such candidates are checked exactly.
+    /// Roots that `f64` can't tell from their neighbours are then found by isolating the real
+    /// roots of what's left exactly, see `exact_rational_roots`
+    ///
+    /// Fails if the approximations don't converge, or with [`MatError::Overflow`] if the exact
+    /// arithmetic overflows, which can't happen with `big_rational`
+    #[allow(clippy::type_complexity)]
+    pub fn rational_roots(&self) -> Result<(Vec<(Rational, usize)>, Polynomial<Rational>), MatError> {
+        let mut roots = Vec::new();
+        let mut rest = self.clone();
+        if rest.degree() == 0 {
+            return Ok((roots, rest));
+        }
+
+        // zero roots, so that the constant term is non-zero
+        let k = rest.coef.iter().take_while(|c| c.is_add_zero()).count();
+        if k > 0 {
+            rest = Polynomial::new(rest.coef[k..].to_vec());
+            roots.push((Rational::add_zero(), k));
+        }
+        if rest.degree() == 0 {
+            return Ok((roots, rest));
+        }
+
+        // divide `x` out of `rest` as many times as it goes, returning the multiplicity
+        let mut divide_out = |rest: &mut Polynomial<Rational>, x: &Rational| -> Result<usize, MatError> {
+            let mut m = 0;
+            while rest.degree() > 0 && checked_eval(rest, x).ok_or(MatError::Overflow)?.is_add_zero() {
+                *rest = checked_deflate(rest, x).ok_or(MatError::Overflow)?;
+                m += 1;
+            }
+            if m > 0 {
+                roots.push((x.clone(), m));
+            }
+            Ok(m)
+        };
+
+        let simple = square_free(&rest).ok_or(MatError::Overflow)?;
+        let (constant, lead) = integer_ends(&simple).ok_or(MatError::Overflow)?;
+        let zero = Integer::from(0);
+        let approx: Polynomial<f64> = Polynomial::new(simple.coef.iter().map(|c| f64::from(c.clone())).collect());
+        let mut tried: HashSet<Rational> = HashSet::new();
+        for z in approx.roots()? {
+            for (p, q) in convergents(z.0, &lead) {
+                if p == zero || constant.clone() % p.clone() != zero || lead.clone() % q.clone() != zero {
+                    continue;
+                }
+                let x = Rational::new(p, q);
+                if tried.insert(x.clone()) {
+                    divide_out(&mut rest, &x)?;
+                }
+            }
+        }
+
+        if rest.degree() > 0 {
+            let simple = square_free(&rest).ok_or(MatError::Overflow)?;
+            for x in exact_rational_roots(&simple).ok_or(MatError::Overflow)? {
+                divide_out(&mut rest, &x)?;
+            }
+        }
+
+        Ok((roots, rest))
+    }
+
+    /// All roots, each repeated by its multiplicity
+    ///
+    /// Rational roots are found exactly by [`rational_roots`](Polynomial::rational_roots)
+    /// first, then the rest from the remaining factor in `f64`
+    pub fn roots(&self) -> Result<Vec<Complex>, MatError> {
+        let (exact, rest) = self.rational_roots()?;
+        let mut roots: Vec<Complex> = exact
+            .into_iter()
+            .flat_map(|(x, m)| std::iter::repeat_n(Complex::from(x), m))
+            .collect();
+        let rest: Polynomial<f64> = Polynomial::new(rest.coef.into_iter().map(f64::from).collect());
+        roots.extend(rest.roots()?);
+        Ok(roots)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rational;
+    use std::ops::Mul;
+
+    fn contains(roots: &[(Complex, usize)], z: Complex, m: usize) -> bool {
+        roots.iter().any(|(x, k)| *k == m && (*x - &z).normal() < 1e-8)
+    }
+
+    #[test]
+    fn test_roots() {
+        // (x-1)^2 (x-2) (x^2+1)
+        let p: Polynomial<f64> = Polynomial::new(vec![-2.0, 5.0, -6.0, 6.0, -4.0, 1.0]);
+        let roots = p.roots_with_multiplicity(1e-4).unwrap();
+        assert_eq!(roots.len(), 4);
+        assert!(contains(&roots, Complex(1.0, 0.0), 2));
+        assert!(contains(&roots, Complex(2.0, 0.0), 1));
+        assert!(contains(&roots, Complex(0.0, 1.0), 1));
+        assert!(contains(&roots, Complex(0.0, -1.0), 1));
+        assert_eq!(p.roots().unwrap().len(), 5);
+
+        assert!(Polynomial::new(vec![3.0]).roots().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_roots_complex() {
+        // (x - i)(x - 2)
+        let p = Polynomial::new(vec![Complex(0.0, 2.0), Complex(-2.0, -1.0), Complex(1.0, 0.0)]);
+        let roots = p.roots_with_multiplicity(1e-4).unwrap();
+        assert!(contains(&roots, Complex(0.0, 1.0), 1));
+        assert!(contains(&roots, Complex(2.0, 0.0), 1));
+    }
+
+    #[test]
+    fn test_rational_roots() {
+        // (x-2)(x+1)(2x-1)
+        let p = Polynomial::new(vec![rational!(2, 1), rational!(-3, 1), rational!(-3, 1), rational!(2, 1)]);
+        let (mut roots, rest) = p.rational_roots().unwrap();
+        roots.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        assert_eq!(
+            roots,
+            vec![(rational!(-1, 1), 1), (rational!(1, 2), 1), (rational!(2, 1), 1)]
+        );
+        assert_eq!(rest.degree(), 0);
+
+        // x (x - 1/3)^2 (x^2 - 2)
+        let p = Polynomial::new(vec![rational!(0, 1), rational!(1, 9), rational!(-2, 3), rational!(1, 1)])
+            .mul(&Polynomial::new(vec![rational!(-2, 1), rational!(0, 1), rational!(1, 1)]));
+        let (roots, rest) = p.rational_roots().unwrap();
+        assert!(roots.contains(&(rational!(0, 1), 1)));
+        assert!(roots.contains(&(rational!(1, 3), 2)));
+        assert_eq!(rest.degree(), 2);
+
+        let all = p.roots().unwrap();
+        assert_eq!(all.len(), 5);
+        let sqrt2 = 2f64.sqrt();
+        for z in [Complex(sqrt2, 0.0), Complex(-sqrt2, 0.0)] {
+            assert!(all.iter().any(|x| (*x - &z).normal() < 1e-12));
+        }
+    }
+
+    #[test]
+    fn test_exact_rational_roots() {
+        // (2x - 1)(x + 3)(x^2 - 2), without the `f64` approximations
+        let p = Polynomial::new(vec![rational!(-1, 1), rational!(2, 1)])
+            .mul(&Polynomial::new(vec![rational!(3, 1), rational!(1, 1)]))
+            .mul(&Polynomial::new(vec![rational!(-2, 1), rational!(0, 1), rational!(1, 1)]));
+        let mut roots = exact_rational_roots(&p).unwrap();
+        roots.sort();
+        assert_eq!(roots, vec![rational!(-3, 1), rational!(1, 2)]);
+
+        // (x - 1)^3 (x + 1), whose square-free part is `x^2 - 1`
+        let p = Polynomial::new(vec![rational!(-1, 1), rational!(2, 1), rational!(0, 1), rational!(-2, 1), rational!(1, 1)]);
+        assert_eq!(square_free(&p).unwrap(), Polynomial::new(vec![rational!(-1, 1), rational!(0, 1), rational!(1, 1)]));
+        let (roots, rest) = p.rational_roots().unwrap();
+        assert!(roots.contains(&(rational!(1, 1), 3)) && roots.contains(&(rational!(-1, 1), 1)));
+        assert_eq!(rest.degree(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "big_rational")]
+    fn test_rational_roots_close() {
+        // (x - 1)(x - r)(x^2 + 1) where `r = 1 + 10^-12` can't be told from `1` in `f64`
+        let big = num_bigint::BigInt::from(10).pow(12);
+        let r = Rational::new(big.clone() + 1, big);
+        let p = Polynomial::new(vec![rational!(1, 1), rational!(0, 1), rational!(1, 1)])
+            .mul(&Polynomial::new(vec![rational!(-1, 1), rational!(1, 1)]))
+            .mul(&Polynomial::new(vec![rational!(0, 1) - &r, rational!(1, 1)]));
+        let (mut roots, rest) = p.rational_roots().unwrap();
+        roots.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        assert_eq!(roots, vec![(rational!(1, 1), 1), (r, 1)]);
+        assert_eq!(rest.degree(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "big_rational")]
+    fn test_rational_roots_large() {
+        // (3x - 1)(x - r)(x^2 + 1), trial division of the constant term would take ages
+        let r = Rational::new(num_bigint::BigInt::from(10).pow(15) + 37, 1.into());
+        let p = Polynomial::new(vec![rational!(1, 1), rational!(0, 1), rational!(1, 1)])
+            .mul(&Polynomial::new(vec![rational!(-1, 1), rational!(3, 1)]))
+            .mul(&Polynomial::new(vec![rational!(0, 1) - &r, rational!(1, 1)]));
+        let (mut roots, rest) = p.rational_roots().unwrap();
+        roots.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        assert_eq!(roots, vec![(rational!(1, 3), 1), (r, 1)]);
+        assert_eq!(rest.degree(), 2);
+    }
+}
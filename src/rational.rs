@@ -1,17 +1,27 @@
+// `Integer` may be a `BigInt`, which is not `Copy`, so values are cloned explicitly
+#![allow(clippy::clone_on_copy)]
+
 use std::cmp::Ordering;
 use std::fmt::Display;
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
 
-#[cfg(not(feature = "i64_rational"))]
-type Integer = i32;
-#[cfg(not(feature = "i64_rational"))]
+#[cfg(not(any(feature = "i64_rational", feature = "big_rational")))]
+pub type Integer = i32;
+#[cfg(not(any(feature = "i64_rational", feature = "big_rational")))]
 pub const INTEGER: &str = "i32";
 
-#[cfg(feature = "i64_rational")]
-type Integer = i64;
-#[cfg(feature = "i64_rational")]
+#[cfg(all(feature = "i64_rational", not(feature = "big_rational")))]
+pub type Integer = i64;
+#[cfg(all(feature = "i64_rational", not(feature = "big_rational")))]
 pub const INTEGER: &str = "i64";
 
+/// With `big_rational`, numerator and denominator are arbitrary-precision, so arithmetic
+/// never overflows. This feature takes precedence over `i64_rational`
+#[cfg(feature = "big_rational")]
+pub type Integer = num_bigint::BigInt;
+#[cfg(feature = "big_rational")]
+pub const INTEGER: &str = "BigInt";
+
 /// A rational number
 ///
 /// Rational(1, 2) = 1/2
@@ -19,22 +29,98 @@ pub const INTEGER: &str = "i64";
 /// 1 will always be (1, 1);
 /// 0 will always be (0, 1);
 /// and only `p` will be negative if `p/q` is negative
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(not(feature = "big_rational"), derive(Copy))]
 pub struct Rational(pub Integer, pub Integer);
 
-fn gcd(m: Integer, n: Integer) -> Integer {
-    if n == 0 {
-        m
+fn zero() -> Integer {
+    Integer::from(0)
+}
+
+fn one() -> Integer {
+    Integer::from(1)
+}
+
+fn abs(m: &Integer) -> Integer {
+    if *m < zero() {
+        -m
+    } else {
+        m.clone()
+    }
+}
+
+pub(crate) fn gcd(m: &Integer, n: &Integer) -> Integer {
+    if *n == zero() {
+        m.clone()
     } else {
-        gcd(n, m % n)
+        gcd(n, &(m % n))
     }
 }
 
+#[cfg(not(feature = "big_rational"))]
+fn checked_int_add(m: &Integer, n: &Integer) -> Option<Integer> {
+    Integer::checked_add(*m, *n)
+}
+#[cfg(not(feature = "big_rational"))]
+fn checked_int_sub(m: &Integer, n: &Integer) -> Option<Integer> {
+    Integer::checked_sub(*m, *n)
+}
+#[cfg(not(feature = "big_rational"))]
+fn checked_int_mul(m: &Integer, n: &Integer) -> Option<Integer> {
+    Integer::checked_mul(*m, *n)
+}
+
+#[cfg(not(feature = "big_rational"))]
+fn checked_int_neg(m: &Integer) -> Option<Integer> {
+    Integer::checked_neg(*m)
+}
+#[cfg(not(feature = "big_rational"))]
+fn checked_int_abs(m: &Integer) -> Option<Integer> {
+    Integer::checked_abs(*m)
+}
+
+#[cfg(feature = "big_rational")]
+fn checked_int_add(m: &Integer, n: &Integer) -> Option<Integer> {
+    Some(m + n)
+}
+#[cfg(feature = "big_rational")]
+fn checked_int_sub(m: &Integer, n: &Integer) -> Option<Integer> {
+    Some(m - n)
+}
+#[cfg(feature = "big_rational")]
+fn checked_int_mul(m: &Integer, n: &Integer) -> Option<Integer> {
+    Some(m * n)
+}
+#[cfg(feature = "big_rational")]
+fn checked_int_neg(m: &Integer) -> Option<Integer> {
+    Some(-m)
+}
+#[cfg(feature = "big_rational")]
+fn checked_int_abs(m: &Integer) -> Option<Integer> {
+    Some(abs(m))
+}
+
+#[cfg(not(feature = "big_rational"))]
+fn ratio_to_f64(p: &Integer, q: &Integer) -> f64 {
+    *p as f64 / *q as f64 // No big deal to go wrong
+}
+
+#[cfg(feature = "big_rational")]
+fn ratio_to_f64(p: &Integer, q: &Integer) -> f64 {
+    use num_traits::ToPrimitive;
+
+    // Drop the lowest bits of huge numbers so that neither overflows `f64`
+    let shift = p.bits().max(q.bits()).saturating_sub(1000);
+    let p = (p >> shift).to_f64().unwrap();
+    let q = (q >> shift).to_f64().unwrap();
+    p / q
+}
+
 #[macro_export]
 /// Create a [`Rational`] by calling its `new` method
 macro_rules! rational {
     ($p:expr, $q:expr) => {
-        $crate::Rational::new($p, $q)
+        $crate::Rational::new(($p).into(), ($q).into())
     };
 }
 
@@ -43,11 +129,11 @@ impl Rational {
     ///
     /// Simplification will be run if using this method
     pub fn new(mut p: Integer, mut q: Integer) -> Self {
-        if q < 0 {
+        if q < zero() {
             p = -p;
             q = -q
         };
-        if q == 0 {
+        if q == zero() {
             panic!("Don't initialize a Rational with {}/0!", p);
         }
 
@@ -63,18 +149,35 @@ impl Rational {
     /// - move the `-` to `p`
     /// - divide `p,q` by `gcd(p,q)`
     fn simplify(&mut self) {
-        if self.0 == 0 {
-            self.1 = 1;
+        if self.0 == zero() {
+            self.1 = one();
             return;
         }
-        if self.1 < 0 {
-            self.0 = -self.0;
-            self.1 = -self.1
+        if self.1 < zero() {
+            self.0 = -&self.0;
+            self.1 = -&self.1;
+        }
+
+        let t = gcd(&abs(&self.0), &abs(&self.1));
+        self.0 /= &t;
+        self.1 /= &t;
+    }
+
+    /// Same as `simplify`, but returns [`None`] if negating `p` or `q` overflows
+    fn checked_simplify(mut self) -> Option<Self> {
+        if self.0 == zero() {
+            self.1 = one();
+            return Some(self);
+        }
+        if self.1 < zero() {
+            self.0 = checked_int_neg(&self.0)?;
+            self.1 = checked_int_neg(&self.1)?;
         }
 
-        let t = gcd(self.0.abs(), self.1.abs());
-        self.0 /= t;
-        self.1 /= t;
+        let t = gcd(&checked_int_abs(&self.0)?, &checked_int_abs(&self.1)?);
+        self.0 /= &t;
+        self.1 /= &t;
+        Some(self)
     }
 
     /// Rational(1, 2).inv() = Rational(2, 1)
@@ -85,14 +188,57 @@ impl Rational {
     pub fn pow(self, n: u32) -> Self {
         Self(self.0.pow(n), self.1.pow(n))
     }
+
+    /// Returns if the denominator is `1`
+    pub fn is_integer(&self) -> bool {
+        self.1 == one()
+    }
+
+    /// `self + rhs`, returning [`None`] on overflow
+    pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        let p = checked_int_add(
+            &checked_int_mul(&self.0, &rhs.1)?,
+            &checked_int_mul(&self.1, &rhs.0)?,
+        )?;
+        let q = checked_int_mul(&self.1, &rhs.1)?;
+        Rational(p, q).checked_simplify()
+    }
+
+    /// `self - rhs`, returning [`None`] on overflow
+    pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        let p = checked_int_sub(
+            &checked_int_mul(&self.0, &rhs.1)?,
+            &checked_int_mul(&self.1, &rhs.0)?,
+        )?;
+        let q = checked_int_mul(&self.1, &rhs.1)?;
+        Rational(p, q).checked_simplify()
+    }
+
+    /// `self * rhs`, returning [`None`] on overflow
+    pub fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        let h = gcd(&self.0, &rhs.1);
+        let (p1, q2) = (&self.0 / &h, &rhs.1 / &h);
+        let h = gcd(&self.1, &rhs.0);
+        let (p2, q1) = (&rhs.0 / &h, &self.1 / &h);
+
+        Rational(checked_int_mul(&p1, &p2)?, checked_int_mul(&q1, &q2)?).checked_simplify()
+    }
+
+    /// `self / rhs`, returning [`None`] on overflow or if `rhs` is zero
+    pub fn checked_div(&self, rhs: &Self) -> Option<Self> {
+        if rhs.0 == zero() {
+            return None;
+        }
+        self.checked_mul(&rhs.clone().inv())
+    }
 }
 
 impl Add<&Self> for Rational {
     type Output = Self;
 
     fn add(self, rhs: &Self) -> Self::Output {
-        let p = self.0 * rhs.1 + self.1 * rhs.0;
-        let q = self.1 * rhs.1;
+        let p = &self.0 * &rhs.1 + &self.1 * &rhs.0;
+        let q = &self.1 * &rhs.1;
         let mut ret = Rational(p, q);
         ret.simplify();
         ret
@@ -103,8 +249,8 @@ impl Sub<&Self> for Rational {
     type Output = Self;
 
     fn sub(self, rhs: &Self) -> Self::Output {
-        let p = self.0 * rhs.1 - self.1 * rhs.0;
-        let q = self.1 * rhs.1;
+        let p = &self.0 * &rhs.1 - &self.1 * &rhs.0;
+        let q = &self.1 * &rhs.1;
         let mut ret = Rational(p, q);
         ret.simplify();
         ret
@@ -116,12 +262,12 @@ impl Mul<&Self> for Rational {
 
     fn mul(self, rhs: &Self) -> Self::Output {
         let (p1, q2) = {
-            let h = gcd(self.0, rhs.1);
-            (self.0 / h, rhs.1 / h)
+            let h = gcd(&self.0, &rhs.1);
+            (&self.0 / &h, &rhs.1 / &h)
         };
         let (p2, q1) = {
-            let h = gcd(self.1, rhs.0);
-            (rhs.0 / h, self.1 / h)
+            let h = gcd(&self.1, &rhs.0);
+            (&rhs.0 / &h, &self.1 / &h)
         };
         let mut ret = Rational(p1 * p2, q1 * q2);
         ret.simplify();
@@ -133,26 +279,24 @@ impl Div<&Self> for Rational {
     type Output = Self;
 
     fn div(self, rhs: &Self) -> Self::Output {
-        if rhs.0 == 0 {
+        if rhs.0 == zero() {
             panic!("Devided by zero");
         }
-        self.mul(&rhs.inv())
+        self.mul(&rhs.clone().inv())
     }
 }
 
 impl From<Rational> for f64 {
     fn from(value: Rational) -> Self {
-        let p: f64 = value.0 as f64;  // No big deal to go wrong
-        let q: f64 = value.1 as f64;
-        p / q
+        ratio_to_f64(&value.0, &value.1)
     }
 }
 
 impl Display for Rational {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.0 == 0 {
+        if self.0 == zero() {
             write!(f, "0")
-        } else if self.1 == 1 {
+        } else if self.1 == one() {
             write!(f, "{}", self.0)
         } else {
             write!(f, "{}/{}", self.0, self.1)
@@ -162,51 +306,57 @@ impl Display for Rational {
 
 impl From<Integer> for Rational {
     fn from(i: Integer) -> Self {
-        Self(i, 1)
+        Self(i, one())
+    }
+}
+
+#[cfg(any(feature = "i64_rational", feature = "big_rational"))]
+impl From<i32> for Rational {
+    fn from(i: i32) -> Self {
+        Self(i.into(), one())
+    }
+}
+
+#[cfg(feature = "big_rational")]
+impl From<i64> for Rational {
+    fn from(i: i64) -> Self {
+        Self(i.into(), one())
     }
 }
 
 impl PartialOrd for Rational {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        (self.0 * other.1).partial_cmp(&(self.1 * other.0))
+        (&self.0 * &other.1).partial_cmp(&(&self.1 * &other.0))
     }
 }
 
 impl Ord for Rational {
     fn cmp(&self, other: &Self) -> Ordering {
-        (self.0 * other.1).cmp(&(self.1 * other.0))
+        (&self.0 * &other.1).cmp(&(&self.1 * &other.0))
     }
 }
 
 impl AddAssign<&Self> for Rational {
     fn add_assign(&mut self, rhs: &Self) {
-        let v = *self + &rhs;
-        self.0 = v.0;
-        self.1 = v.1;
+        *self = self.clone() + rhs;
     }
 }
 
 impl SubAssign<&Self> for Rational {
     fn sub_assign(&mut self, rhs: &Self) {
-        let v = *self - &rhs;
-        self.0 = v.0;
-        self.1 = v.1;
+        *self = self.clone() - rhs;
     }
 }
 
 impl MulAssign<&Self> for Rational {
     fn mul_assign(&mut self, rhs: &Self) {
-        let v = *self * &rhs;
-        self.0 = v.0;
-        self.1 = v.1;
+        *self = self.clone() * rhs;
     }
 }
 
 impl DivAssign<&Self> for Rational {
     fn div_assign(&mut self, rhs: &Self) {
-        let v = *self / rhs;
-        self.0 = v.0;
-        self.1 = v.1;
+        *self = self.clone() / rhs;
     }
 }
 
@@ -214,23 +364,40 @@ use crate::element::*;
 
 impl AddZero for Rational {
     fn add_zero() -> Self {
-        Rational(0, 1)
+        Rational(zero(), one())
     }
     fn is_add_zero(&self) -> bool {
-        self.0 == 0
+        self.0 == zero()
     }
 }
 
 impl MulZero for Rational {
     fn mul_zero() -> Self {
-        Rational(1, 1)
+        Rational(one(), one())
     }
     fn is_mul_zero(&self) -> bool {
-        self.0 == 1 && self.1 == 1
+        self.0 == one() && self.1 == one()
     }
 }
 
-impl LinearElem for Rational {}
+impl Normal for Rational {
+    fn normal(&self) -> f64 {
+        f64::from(self.clone()).abs()
+    }
+}
+
+impl LinearElem for Rational {
+    par_row_blocks!();
+    fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        Rational::checked_add(self, rhs)
+    }
+    fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        Rational::checked_sub(self, rhs)
+    }
+    fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        Rational::checked_mul(self, rhs)
+    }
+}
 
 #[cfg(test)]
 mod test {
@@ -238,8 +405,8 @@ mod test {
     #[test]
     fn test_new() {
         let a = rational!(6, 12);
-        assert_eq!(a.0, 1);
-        assert_eq!(a.1, 2);
+        assert_eq!(a.0, 1.into());
+        assert_eq!(a.1, 2.into());
     }
 
     #[test]
@@ -271,6 +438,27 @@ mod test {
         assert_eq!(rational!(4, 9) == rational!(12, 27), true);
         assert_eq!(rational!(1, 2) == rational!(1, 2), true);
     }
+    #[test]
+    fn test_checked() {
+        assert_eq!(rational!(2, 3).checked_add(&rational!(5, 4)), Some(rational!(23, 12)));
+        assert_eq!(rational!(2, 3).checked_sub(&rational!(5, 4)), Some(rational!(-7, 12)));
+        assert_eq!(rational!(2, 3).checked_mul(&rational!(9, 8)), Some(rational!(3, 4)));
+        assert_eq!(rational!(2, 3).checked_div(&rational!(7, 9)), Some(rational!(6, 7)));
+        assert_eq!(rational!(2, 3).checked_div(&rational!(0, 1)), None);
+    }
+    #[test]
+    #[cfg(not(feature = "big_rational"))]
+    fn test_checked_overflow() {
+        use super::{Integer, Rational};
+
+        let max = Rational::from(Integer::MAX);
+        assert_eq!(max.checked_add(&rational!(1, 1)), None);
+        assert_eq!(max.checked_mul(&rational!(2, 1)), None);
+        assert_eq!(rational!(1, 2).checked_add(&rational!(1, Integer::MAX)), None);
+        // the sum is `Integer::MIN`, whose absolute value overflows
+        let half = Rational::from(Integer::MIN / 2);
+        assert_eq!(half.checked_add(&half), None);
+    }
 }
 
 mod from_str {
@@ -288,7 +476,7 @@ mod from_str {
                 let (p, mut q) = self.split_at(idx);
                 q = &q[1..];
                 if let (Ok(p), Ok(q)) = (p.parse(), q.parse()) {
-                    if q == 0 { return Err(ParseError::ZeroDivision) }
+                    if q == zero() { return Err(ParseError::ZeroDivision) }
                     return Ok(Rational::new(p, q));
                 }
             }